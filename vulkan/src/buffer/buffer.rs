@@ -2,7 +2,7 @@ use crate::{
 	alloc, backend,
 	buffer::Builder,
 	flags::{BufferUsage, IndexType, MemoryLocation, SharingMode},
-	utility::{self, HandledObject},
+	utility::{self, AccessState, HandledObject},
 };
 use std::sync;
 
@@ -19,6 +19,7 @@ pub struct Buffer {
 	internal: backend::vk::Buffer,
 	allocation_handle: Option<gpu_allocator::vulkan::Allocation>,
 	allocator: sync::Arc<alloc::Allocator>,
+	access: Option<sync::Mutex<AccessState>>,
 	builder: Builder,
 }
 
@@ -39,6 +40,10 @@ impl Buffer {
 			allocator,
 			internal,
 			allocation_handle: Some(allocation_handle),
+			access: match builder.tracks_access() {
+				true => Some(sync::Mutex::new(AccessState::default())),
+				false => None,
+			},
 			builder,
 		}
 	}
@@ -124,6 +129,67 @@ impl Buffer {
 		self.builder.index_type()
 	}
 
+	/// The ways the buffer can be used, as declared when it was built.
+	pub fn usage(&self) -> BufferUsage {
+		self.builder.usage()
+	}
+
+	/// Returns an error if the buffer was not created with all of the provided usage flags.
+	/// `action` describes what the buffer is being used for, and is included in the error.
+	pub fn require_usage(&self, usage: BufferUsage, action: &str) -> utility::Result<()> {
+		use utility::NamedObject;
+		if self.usage().contains(usage) {
+			return Ok(());
+		}
+		Err(utility::Error::InvalidBufferUsage(format!(
+			"{:?} cannot be used for {} without {:?} usage",
+			self.name(),
+			action,
+			usage
+		)))
+	}
+
+	/// Returns true if the buffer was [`built`](Builder::with_access_tracking) to track its most recent access.
+	pub fn is_access_tracked(&self) -> bool {
+		self.access.is_some()
+	}
+
+	/// Returns the tracked access of the buffer, as of the most recently recorded command.
+	/// Returns None if the buffer is not tracked.
+	pub fn tracked_access(&self) -> Option<AccessState> {
+		self.access.as_ref().map(|access| *access.lock().unwrap())
+	}
+
+	/// Overrides the tracked access of the buffer.
+	/// Used when the buffer is accessed outside of a [`barrier`](crate::command::barrier),
+	/// such as by the host after a submission has completed.
+	pub fn set_tracked_access(&self, state: AccessState) {
+		if let Some(access) = &self.access {
+			*access.lock().unwrap() = state;
+		}
+	}
+
+	/// Moves the buffer into the `next` access state,
+	/// returning the previous state if a barrier is required to do so.
+	pub(crate) fn transition_tracked(
+		&self,
+		next: AccessState,
+	) -> utility::Result<Option<AccessState>> {
+		use utility::NamedObject;
+		let access = match &self.access {
+			Some(access) => access,
+			None => return Err(utility::Error::UntrackedResource(self.name().clone())),
+		};
+		let mut current = access.lock().unwrap();
+		let prev = *current;
+		if !prev.conflicts_with(&next) {
+			*current = prev.merge(&next);
+			return Ok(None);
+		}
+		*current = next;
+		Ok(Some(prev))
+	}
+
 	pub fn rename(&self, name: &str) {
 		// NOTE: Intentionally does not modify the builder because that would mean marking the buffer as mutable
 		// and there is no use case for a persistent name change (rename and then expand the same buffer).
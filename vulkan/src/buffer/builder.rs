@@ -20,6 +20,7 @@ pub struct Builder {
 	sharing_mode: SharingMode,
 	queue_families: Vec<u32>,
 	index_type: Option<IndexType>,
+	track_access: bool,
 }
 
 impl Default for Builder {
@@ -33,6 +34,7 @@ impl Default for Builder {
 			sharing_mode: SharingMode::EXCLUSIVE,
 			queue_families: Vec::new(),
 			index_type: None,
+			track_access: false,
 		}
 	}
 }
@@ -75,6 +77,10 @@ impl Builder {
 		self
 	}
 
+	pub(crate) fn usage(&self) -> BufferUsage {
+		self.usage
+	}
+
 	/// Sets the index type that the buffer contains.
 	/// Only used/valid if using an [`index buffer`](BufferUsage::INDEX_BUFFER).
 	pub fn with_index_type(mut self, kind: Option<IndexType>) -> Self {
@@ -105,6 +111,18 @@ impl Builder {
 		self.supress_drop_log = true;
 		self
 	}
+
+	/// Mutates the builder so that the buffer tracks its most recent access,
+	/// allowing [`transitions`](crate::command::Buffer::transition_buffer) to compute the barriers they require.
+	pub fn with_access_tracking(mut self) -> Self {
+		self.track_access = true;
+		self
+	}
+
+	/// Returns true if the buffer should track its most recent access.
+	pub(crate) fn tracks_access(&self) -> bool {
+		self.track_access
+	}
 }
 
 impl utility::NameableBuilder for Builder {
@@ -1,30 +1,43 @@
 //! Structs which handle describing barriers sent to [`mark_pipeline_barrier`](crate::command::Buffer::mark_pipeline_barrier).
 
 use crate::{
-	backend, buffer,
-	flags::{Access, ImageLayout, PipelineStage},
+	backend, buffer, command,
+	flags::{AccessSet, Dependency, ImageLayout, StageSet},
 	image,
 	structs::subresource,
+	utility::{self, AccessState, BoundObject, NamedObject},
 };
-use enumset::EnumSet;
 use std::sync;
 
 /// A collection of barriers for a pipeline which is created to [`mark a pipeline barrier`](crate::command::Buffer::mark_pipeline_barrier).
+#[derive(Clone)]
 pub struct Pipeline {
-	pub(crate) src_stage: PipelineStage,
-	pub(crate) dst_stage: PipelineStage,
+	pub(crate) src_stages: StageSet,
+	pub(crate) dst_stages: StageSet,
+	pub(crate) dependency: Dependency,
 	pub(crate) kinds: Vec<Kind>,
 }
 
 impl Pipeline {
-	pub fn new(src: PipelineStage, dst: PipelineStage) -> Self {
+	/// Creates a barrier between the stages of commands recorded before the barrier (`src`)
+	/// and the stages of commands recorded after it (`dst`).
+	/// Either can be a single [`PipelineStage`](crate::flags::PipelineStage) or any [`StageSet`].
+	pub fn new(src: impl Into<StageSet>, dst: impl Into<StageSet>) -> Self {
 		Self {
-			src_stage: src,
-			dst_stage: dst,
+			src_stages: src.into(),
+			dst_stages: dst.into(),
+			dependency: Dependency::empty(),
 			kinds: Vec::new(),
 		}
 	}
 
+	/// Includes flags which change how the dependency is formed
+	/// (i.e. [`BY_REGION`](Dependency::BY_REGION) for framebuffer-local dependencies).
+	pub fn with_dependency(mut self, flags: Dependency) -> Self {
+		self.dependency |= flags;
+		self
+	}
+
 	/// Includes a specific barrier kind in the barriers being marked.
 	pub fn with(mut self, kind: Kind) -> Self {
 		self.kinds.push(kind);
@@ -32,7 +45,95 @@ impl Pipeline {
 	}
 }
 
+impl Pipeline {
+	/// Updates the tracked state of the buffers and images in the barrier to their destination state.
+	pub(crate) fn apply_to_tracked(&self) {
+		for kind in self.kinds.iter() {
+			match kind {
+				Kind::Memory(_) => {}
+				Kind::Buffer(info) => info.apply_to_tracked(self.dst_stages),
+				Kind::Image(info) => info.apply_to_tracked(self.dst_stages),
+			}
+		}
+	}
+
+	pub(crate) fn as_vk(&self) -> Barriers {
+		let mut barriers = Barriers::default();
+		for kind in self.kinds.iter() {
+			match kind {
+				Kind::Memory(info) => barriers.memory.push(info.as_vk()),
+				Kind::Buffer(info) => barriers.buffers.push(info.as_vk()),
+				Kind::Image(info) => barriers.images.push(info.as_vk()),
+			}
+		}
+		barriers
+	}
+
+	pub(crate) fn as_vk2(&self) -> Barriers2 {
+		let (src, dst) = (self.src_stages, self.dst_stages);
+		let mut barriers = Barriers2 {
+			dependency: self.dependency,
+			..Default::default()
+		};
+		for kind in self.kinds.iter() {
+			match kind {
+				Kind::Memory(info) => barriers.memory.push(info.as_vk2(src, dst)),
+				Kind::Buffer(info) => barriers.buffers.push(info.as_vk2(src, dst)),
+				Kind::Image(info) => barriers.images.push(info.as_vk2(src, dst)),
+			}
+		}
+		barriers
+	}
+
+	/// The source and destination stages as legacy flags.
+	/// The legacy commands do not allow empty stage masks, which synchronization2 uses for "no stages".
+	pub(crate) fn legacy_stages(
+		&self,
+	) -> (
+		backend::vk::PipelineStageFlags,
+		backend::vk::PipelineStageFlags,
+	) {
+		let src = match self.src_stages.is_empty() {
+			true => backend::vk::PipelineStageFlags::TOP_OF_PIPE,
+			false => self.src_stages.as_legacy(),
+		};
+		let dst = match self.dst_stages.is_empty() {
+			true => backend::vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+			false => self.dst_stages.as_legacy(),
+		};
+		(src, dst)
+	}
+}
+
+/// The legacy barriers of a [`Pipeline`] barrier.
+#[derive(Default)]
+pub(crate) struct Barriers {
+	pub(crate) memory: Vec<backend::vk::MemoryBarrier>,
+	pub(crate) buffers: Vec<backend::vk::BufferMemoryBarrier>,
+	pub(crate) images: Vec<backend::vk::ImageMemoryBarrier>,
+}
+
+/// The synchronization2 barriers of a [`Pipeline`] barrier, which are borrowed by its [`dependency info`](Barriers2::dependency_info).
+#[derive(Default)]
+pub(crate) struct Barriers2 {
+	dependency: Dependency,
+	memory: Vec<backend::vk::MemoryBarrier2>,
+	buffers: Vec<backend::vk::BufferMemoryBarrier2>,
+	images: Vec<backend::vk::ImageMemoryBarrier2>,
+}
+
+impl Barriers2 {
+	pub(crate) fn dependency_info(&self) -> backend::vk::DependencyInfoBuilder<'_> {
+		backend::vk::DependencyInfo::builder()
+			.dependency_flags(self.dependency)
+			.memory_barriers(&self.memory[..])
+			.buffer_memory_barriers(&self.buffers[..])
+			.image_memory_barriers(&self.images[..])
+	}
+}
+
 /// The kinds of barriers that can be sent to [`mark_pipeline_barrier`](crate::command::Buffer::mark_pipeline_barrier).
+#[derive(Clone)]
 pub enum Kind {
 	/// A barrier around a specific segment of memory.
 	Memory(Memory),
@@ -42,92 +143,214 @@ pub enum Kind {
 	Image(Image),
 }
 
-/// Defines the access masks that create a [`memory barrier`](Kind::Memory).
+/// Defines the access masks that create a [`memory barrier`](Kind::Memory),
+/// which applies to all memory accessed by the stages of the [`Pipeline`] barrier.
+#[derive(Clone)]
 pub struct Memory {
-	src_access: EnumSet<Access>,
-	dst_access: EnumSet<Access>,
+	pub(crate) src_access: AccessSet,
+	pub(crate) dst_access: AccessSet,
 }
 
 impl Default for Memory {
 	fn default() -> Self {
 		Self {
-			src_access: EnumSet::empty(),
-			dst_access: EnumSet::empty(),
+			src_access: AccessSet::empty(),
+			dst_access: AccessSet::empty(),
 		}
 	}
 }
 
-impl Into<backend::vk::MemoryBarrier> for Memory {
-	fn into(self) -> backend::vk::MemoryBarrier {
+impl Memory {
+	/// Includes a provided access mask that is required for the source access.
+	pub fn requires(mut self, access: impl Into<AccessSet>) -> Self {
+		self.src_access |= access.into();
+		self
+	}
+
+	/// Includes a provided access mask that is required for the destination access.
+	pub fn prevents(mut self, access: impl Into<AccessSet>) -> Self {
+		self.dst_access |= access.into();
+		self
+	}
+}
+
+impl Memory {
+	pub(crate) fn as_vk(&self) -> backend::vk::MemoryBarrier {
 		backend::vk::MemoryBarrier::builder()
-			.src_access_mask(Access::fold(&self.src_access))
-			.dst_access_mask(Access::fold(&self.dst_access))
+			.src_access_mask(self.src_access.as_legacy())
+			.dst_access_mask(self.dst_access.as_legacy())
+			.build()
+	}
+
+	pub(crate) fn as_vk2(&self, src: StageSet, dst: StageSet) -> backend::vk::MemoryBarrier2 {
+		backend::vk::MemoryBarrier2::builder()
+			.src_stage_mask(src.as_vk2())
+			.src_access_mask(self.src_access.as_vk2())
+			.dst_stage_mask(dst.as_vk2())
+			.dst_access_mask(self.dst_access.as_vk2())
 			.build()
 	}
 }
 
 /// Defines the access masks, queues, and buffer data that creates a [`buffer barrier`](Kind::Buffer).
+#[derive(Clone)]
 pub struct Buffer {
-	src_access: EnumSet<Access>,
-	src_queue_family: u32,
+	pub(crate) src_access: AccessSet,
+	pub(crate) src_queue_family: u32,
 
-	dst_access: EnumSet<Access>,
-	dst_queue_family: u32,
+	pub(crate) dst_access: AccessSet,
+	pub(crate) dst_queue_family: u32,
 
-	buffer: sync::Weak<buffer::Buffer>,
-	offset: usize,
-	size: usize,
+	pub(crate) buffer: sync::Arc<buffer::Buffer>,
+	pub(crate) offset: usize,
+	pub(crate) size: Option<usize>,
 }
 
-impl Default for Buffer {
-	fn default() -> Self {
+impl Buffer {
+	/// Creates a barrier for the entire buffer. The buffer is kept alive until the barrier is marked.
+	pub fn new(buffer: &sync::Arc<buffer::Buffer>) -> Self {
 		Self {
-			src_access: EnumSet::empty(),
+			src_access: AccessSet::empty(),
 			src_queue_family: u32::MAX, // queue is ignored
-			dst_access: EnumSet::empty(),
+			dst_access: AccessSet::empty(),
 			dst_queue_family: u32::MAX, // queue is ignored
-			buffer: sync::Weak::new(),
+			buffer: buffer.clone(),
 			offset: 0,
-			size: 0,
+			size: None, // whole size
 		}
 	}
-}
 
-impl Buffer {
+	/// Includes a provided access mask that is required for the source access.
+	pub fn requires(mut self, access: impl Into<AccessSet>) -> Self {
+		self.src_access |= access.into();
+		self
+	}
+
+	/// Includes a provided access mask that is required for the destination access.
+	pub fn prevents(mut self, access: impl Into<AccessSet>) -> Self {
+		self.dst_access |= access.into();
+		self
+	}
+
+	pub(crate) fn with_access(mut self, src: AccessSet, dst: AccessSet) -> Self {
+		self.src_access |= src;
+		self.dst_access |= dst;
+		self
+	}
+
+	/// Transfers ownership of the resource from one queue family to another.
+	/// The same barrier must be recorded on a queue of each family
+	/// (see [`OwnershipTransfer`](crate::command::OwnershipTransfer) for a helper which does this).
+	pub fn with_queue_transfer(mut self, src_family: usize, dst_family: usize) -> Self {
+		self.src_queue_family = src_family as u32;
+		self.dst_queue_family = dst_family as u32;
+		self
+	}
+
+	/// Sets the bytes of the buffer that are affected.
+	/// An unbounded end includes the remainder of the buffer (i.e. `VK_WHOLE_SIZE`).
+	/// The entire buffer is affected by default.
+	///
+	/// Returns an error if the range is empty, inverted, or extends past the end of the buffer.
+	pub fn with_region(
+		mut self,
+		range: impl std::ops::RangeBounds<usize>,
+	) -> utility::Result<Self> {
+		let (offset, size) = region(range, self.buffer.size()).map_err(|err| {
+			utility::Error::InvalidBufferUsage(format!(
+				"{:?} barrier region is invalid: {}",
+				self.buffer.name(),
+				err
+			))
+		})?;
+		self.offset = offset;
+		self.size = size;
+		Ok(self)
+	}
+
 	pub(crate) fn as_vk(&self) -> backend::vk::BufferMemoryBarrier {
 		backend::vk::BufferMemoryBarrier::builder()
-			.src_access_mask(Access::fold(&self.src_access))
+			.src_access_mask(self.src_access.as_legacy())
 			.src_queue_family_index(self.src_queue_family)
-			.dst_access_mask(Access::fold(&self.dst_access))
+			.dst_access_mask(self.dst_access.as_legacy())
 			.dst_queue_family_index(self.dst_queue_family)
-			.buffer(**self.buffer.upgrade().unwrap())
+			.buffer(**self.buffer)
 			.offset(self.offset as u64)
-			.size(self.size as u64)
+			.size(self.vk_size())
 			.build()
 	}
+
+	pub(crate) fn as_vk2(&self, src: StageSet, dst: StageSet) -> backend::vk::BufferMemoryBarrier2 {
+		backend::vk::BufferMemoryBarrier2::builder()
+			.src_stage_mask(src.as_vk2())
+			.src_access_mask(self.src_access.as_vk2())
+			.src_queue_family_index(self.src_queue_family)
+			.dst_stage_mask(dst.as_vk2())
+			.dst_access_mask(self.dst_access.as_vk2())
+			.dst_queue_family_index(self.dst_queue_family)
+			.buffer(**self.buffer)
+			.offset(self.offset as u64)
+			.size(self.vk_size())
+			.build()
+	}
+
+	fn vk_size(&self) -> u64 {
+		self.size
+			.map_or(backend::vk::WHOLE_SIZE, |size| size as u64)
+	}
+
+	/// Updates the tracked access of the buffer (if it is tracked) to match the result of the barrier.
+	pub(crate) fn apply_to_tracked(&self, dst_stages: StageSet) {
+		self.buffer
+			.set_tracked_access(AccessState::new(dst_stages, self.dst_access));
+	}
+}
+
+/// Returns the offset and size (None for the remainder of the buffer) of a range of bytes
+/// within a buffer of `buffer_size` bytes, or an error if the range is empty or not within the buffer.
+fn region(
+	range: impl std::ops::RangeBounds<usize>,
+	buffer_size: usize,
+) -> Result<(usize, Option<usize>), String> {
+	match command::fill_range(range)? {
+		(offset, None) if offset < buffer_size => Ok((offset, None)),
+		(offset, None) => Err(format!(
+			"offset {} is not within the {} byte buffer",
+			offset, buffer_size
+		)),
+		(offset, Some(0)) => Err(format!("range starting at {} is empty", offset)),
+		(offset, Some(size)) => match offset.checked_add(size) {
+			Some(end) if end <= buffer_size => Ok((offset, Some(size))),
+			_ => Err(format!(
+				"range of {} bytes at {} exceeds the {} byte buffer",
+				size, offset, buffer_size
+			)),
+		},
+	}
 }
 
 /// Defines the access masks, queues, and image data that creates a [`image barrier`](Kind::Image).
+#[derive(Clone)]
 pub struct Image {
-	src_access: EnumSet<Access>,
-	src_queue_family: u32,
+	pub(crate) src_access: AccessSet,
+	pub(crate) src_queue_family: u32,
 
-	dst_access: EnumSet<Access>,
-	dst_queue_family: u32,
+	pub(crate) dst_access: AccessSet,
+	pub(crate) dst_queue_family: u32,
 
-	image: sync::Weak<image::Image>,
-	old_layout: ImageLayout,
-	new_layout: ImageLayout,
-	range: subresource::Range,
+	pub(crate) image: sync::Weak<image::Image>,
+	pub(crate) old_layout: ImageLayout,
+	pub(crate) new_layout: ImageLayout,
+	pub(crate) range: subresource::Range,
 }
 
 impl Default for Image {
 	fn default() -> Self {
 		Self {
-			src_access: EnumSet::empty(),
+			src_access: AccessSet::empty(),
 			src_queue_family: u32::MAX, // queue is ignored
 
-			dst_access: EnumSet::empty(),
+			dst_access: AccessSet::empty(),
 			dst_queue_family: u32::MAX, // queue is ignored
 
 			image: sync::Weak::new(),
@@ -140,14 +363,14 @@ impl Default for Image {
 
 impl Image {
 	/// Includes a provided access mask that is required for the source access.
-	pub fn requires(mut self, access: Access) -> Self {
-		self.src_access |= access;
+	pub fn requires(mut self, access: impl Into<AccessSet>) -> Self {
+		self.src_access |= access.into();
 		self
 	}
 
 	/// Includes a provided access mask that is required for the destination access.
-	pub fn prevents(mut self, access: Access) -> Self {
-		self.dst_access |= access;
+	pub fn prevents(mut self, access: impl Into<AccessSet>) -> Self {
+		self.dst_access |= access.into();
 		self
 	}
 
@@ -170,11 +393,35 @@ impl Image {
 		self
 	}
 
+	/// Transfers ownership of the resource from one queue family to another.
+	/// The same barrier must be recorded on a queue of each family
+	/// (see [`OwnershipTransfer`](crate::command::OwnershipTransfer) for a helper which does this).
+	pub fn with_queue_transfer(mut self, src_family: usize, dst_family: usize) -> Self {
+		self.src_queue_family = src_family as u32;
+		self.dst_queue_family = dst_family as u32;
+		self
+	}
+
+	pub(crate) fn with_access(mut self, src: AccessSet, dst: AccessSet) -> Self {
+		self.src_access |= src;
+		self.dst_access |= dst;
+		self
+	}
+
+	/// Updates the tracked state of the image (if it is tracked) to match the result of the barrier.
+	pub(crate) fn apply_to_tracked(&self, dst_stages: StageSet) {
+		if let Some(image) = self.image.upgrade() {
+			let access = AccessState::new(dst_stages, self.dst_access);
+			let state = image::SubresourceState::new(self.new_layout, access);
+			image.set_tracked_state(self.range, state);
+		}
+	}
+
 	pub(crate) fn as_vk(&self) -> backend::vk::ImageMemoryBarrier {
 		backend::vk::ImageMemoryBarrier::builder()
-			.src_access_mask(Access::fold(&self.src_access))
+			.src_access_mask(self.src_access.as_legacy())
 			.src_queue_family_index(self.src_queue_family)
-			.dst_access_mask(Access::fold(&self.dst_access))
+			.dst_access_mask(self.dst_access.as_legacy())
 			.dst_queue_family_index(self.dst_queue_family)
 			.image(**self.image.upgrade().unwrap())
 			.old_layout(self.old_layout.into())
@@ -182,4 +429,169 @@ impl Image {
 			.subresource_range(self.range.into())
 			.build()
 	}
+
+	pub(crate) fn as_vk2(&self, src: StageSet, dst: StageSet) -> backend::vk::ImageMemoryBarrier2 {
+		backend::vk::ImageMemoryBarrier2::builder()
+			.src_stage_mask(src.as_vk2())
+			.src_access_mask(self.src_access.as_vk2())
+			.src_queue_family_index(self.src_queue_family)
+			.dst_stage_mask(dst.as_vk2())
+			.dst_access_mask(self.dst_access.as_vk2())
+			.dst_queue_family_index(self.dst_queue_family)
+			.image(**self.image.upgrade().unwrap())
+			.old_layout(self.old_layout.into())
+			.new_layout(self.new_layout.into())
+			.subresource_range(self.range.into())
+			.build()
+	}
+}
+
+/// Describes the next use of a resource whose state is tracked
+/// (an [`Image`](image::Builder::with_state_tracking) or [`Buffer`](buffer::Builder::with_access_tracking)),
+/// so that [`transition_all`](crate::command::Buffer::transition_all) can compute the barriers required
+/// from the state the resource was last used in.
+#[derive(Clone)]
+pub enum Transition {
+	Image {
+		image: sync::Arc<image::Image>,
+		range: subresource::Range,
+		next: image::SubresourceState,
+	},
+	Buffer {
+		buffer: sync::Arc<buffer::Buffer>,
+		next: AccessState,
+	},
+}
+
+impl Transition {
+	/// Transitions all of the subresources of an image into a layout, for use by some stages and accesses.
+	pub fn image(
+		image: &sync::Arc<image::Image>,
+		layout: ImageLayout,
+		stages: impl Into<StageSet>,
+		access: impl Into<AccessSet>,
+	) -> Self {
+		Self::Image {
+			image: image.clone(),
+			range: subresource::Range::default().mips(..).layers(..),
+			next: image::SubresourceState::new(layout, AccessState::new(stages, access)),
+		}
+	}
+
+	/// Transitions a buffer for use by some stages and accesses.
+	pub fn buffer(
+		buffer: &sync::Arc<buffer::Buffer>,
+		stages: impl Into<StageSet>,
+		access: impl Into<AccessSet>,
+	) -> Self {
+		Self::Buffer {
+			buffer: buffer.clone(),
+			next: AccessState::new(stages, access),
+		}
+	}
+
+	/// Limits an image transition to a range of subresources.
+	/// Ranges without an aspect use all of the aspects of the image's format.
+	/// Has no effect on buffer transitions.
+	pub fn with_range(mut self, subresources: subresource::Range) -> Self {
+		if let Self::Image { range, .. } = &mut self {
+			*range = subresources;
+		}
+		self
+	}
+
+	/// Returns an error if the resource being transitioned does not track its state.
+	pub(crate) fn validate(&self) -> utility::Result<()> {
+		let (tracked, name) = match self {
+			Self::Image { image, .. } => (image.is_state_tracked(), image.name()),
+			Self::Buffer { buffer, .. } => (buffer.is_access_tracked(), buffer.name()),
+		};
+		match tracked {
+			true => Ok(()),
+			false => Err(utility::Error::UntrackedResource(name.clone())),
+		}
+	}
+
+	pub(crate) fn bound_object(&self) -> BoundObject {
+		match self {
+			Self::Image { image, .. } => image.clone(),
+			Self::Buffer { buffer, .. } => buffer.clone(),
+		}
+	}
+
+	/// Returns the pipeline stages the resource is used in after the transition.
+	pub(crate) fn stages(&self) -> StageSet {
+		match self {
+			Self::Image { next, .. } => next.access.stages,
+			Self::Buffer { next, .. } => next.stages,
+		}
+	}
+
+	/// Moves the tracked state of the resource to the next state,
+	/// returning the barriers required and the stages which must complete before them.
+	pub(crate) fn apply(&self) -> utility::Result<(StageSet, Vec<Kind>)> {
+		let mut src_stages = StageSet::empty();
+		let mut barriers = Vec::new();
+		match self {
+			Self::Image { image, range, next } => {
+				let aspect = match range.aspect().is_empty() {
+					true => subresource::Range::default().with_format_aspects(image.format()),
+					false => subresource::Range::default().with_aspect(range.aspect()),
+				};
+				for change in image.transition_tracked(range, *next)?.into_iter() {
+					src_stages |= change.prev.access.stages;
+					barriers.push(Kind::Image(
+						Image::default()
+							.with_image(sync::Arc::downgrade(image))
+							.with_layout(change.prev.layout, next.layout)
+							.with_range(
+								aspect
+									.mips(change.mip_level..=change.mip_level)
+									.layers(change.array_layers),
+							)
+							.with_access(change.prev.access.writes(), next.access.access),
+					));
+				}
+			}
+			Self::Buffer { buffer, next } => {
+				if let Some(prev) = buffer.transition_tracked(*next)? {
+					src_stages |= prev.stages;
+					barriers.push(Kind::Buffer(
+						Buffer::new(buffer).with_access(prev.writes(), next.access),
+					));
+				}
+			}
+		}
+		Ok((src_stages, barriers))
+	}
+}
+
+#[cfg(test)]
+mod regions {
+	use super::*;
+
+	#[test]
+	fn bounded_and_unbounded() {
+		assert_eq!(region(.., 16), Ok((0, None)));
+		assert_eq!(region(4.., 16), Ok((4, None)));
+		assert_eq!(region(4..12, 16), Ok((4, Some(8))));
+		assert_eq!(region(4..=11, 16), Ok((4, Some(8))));
+		assert_eq!(region(0..16, 16), Ok((0, Some(16))));
+	}
+
+	#[test]
+	#[allow(clippy::reversed_empty_ranges)]
+	fn empty_and_inverted_ranges_are_rejected() {
+		assert!(region(8..8, 16).is_err());
+		assert!(region(8..3, 16).is_err());
+		assert!(region(8..=3, 16).is_err());
+		assert!(region(0..=usize::MAX, 16).is_err());
+	}
+
+	#[test]
+	fn ranges_must_be_within_the_buffer() {
+		assert!(region(8..17, 16).is_err());
+		assert!(region(16.., 16).is_err());
+		assert!(region(usize::MAX - 1..usize::MAX, 16).is_err());
+	}
 }
@@ -0,0 +1,201 @@
+use crate::{backend::vk, flags};
+
+/// The number of bind commands a [`command buffer`](crate::command::Buffer) has recorded since it was begun,
+/// and the number which were skipped because they would have re-bound the state that was already bound.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BindStatistics {
+	pub emitted: usize,
+	pub filtered: usize,
+}
+
+/// The pipelines, descriptor sets, and buffers which are currently bound in a command buffer,
+/// used to filter out binds which would not change anything.
+#[derive(Default)]
+pub(crate) struct BindState {
+	pipelines: Vec<(flags::PipelineBindPoint, vk::Pipeline)>,
+	descriptor_sets: Vec<BoundSet>,
+	vertex_buffers: Vec<Option<(vk::Buffer, u64)>>,
+	index_buffer: Option<(vk::Buffer, u64, vk::IndexType)>,
+	statistics: BindStatistics,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct BoundSet {
+	bind_point: flags::PipelineBindPoint,
+	layout: vk::PipelineLayout,
+	index: u32,
+	set: vk::DescriptorSet,
+}
+
+impl BindState {
+	pub fn statistics(&self) -> BindStatistics {
+		self.statistics
+	}
+
+	/// Forgets everything which is bound, i.e. when the buffer is begun or after executing secondaries,
+	/// which leave the bound state undefined.
+	pub fn invalidate(&mut self) {
+		self.pipelines.clear();
+		self.descriptor_sets.clear();
+		self.vertex_buffers.clear();
+		self.index_buffer = None;
+	}
+
+	/// Clears the bound state and the statistics.
+	pub fn reset(&mut self) {
+		self.invalidate();
+		self.statistics = BindStatistics::default();
+	}
+
+	/// Returns true if the bind must be recorded, counting it as emitted or filtered.
+	fn record(&mut self, changed: bool) -> bool {
+		match changed {
+			true => self.statistics.emitted += 1,
+			false => self.statistics.filtered += 1,
+		}
+		changed
+	}
+
+	pub fn bind_pipeline(
+		&mut self,
+		bind_point: flags::PipelineBindPoint,
+		pipeline: vk::Pipeline,
+	) -> bool {
+		let bound = self
+			.pipelines
+			.iter_mut()
+			.find(|(point, _)| *point == bind_point);
+		let changed = match bound {
+			Some((_, bound)) if *bound == pipeline => false,
+			Some((_, bound)) => {
+				*bound = pipeline;
+				true
+			}
+			None => {
+				self.pipelines.push((bind_point, pipeline));
+				true
+			}
+		};
+		self.record(changed)
+	}
+
+	/// Binding sets with a different layout can disturb any other sets bound for the bind point,
+	/// so those are forgotten whenever the layout changes.
+	pub fn bind_descriptor_sets(
+		&mut self,
+		bind_point: flags::PipelineBindPoint,
+		layout: vk::PipelineLayout,
+		first_index: u32,
+		sets: &[vk::DescriptorSet],
+	) -> bool {
+		let bound_sets = sets
+			.iter()
+			.enumerate()
+			.map(|(i, set)| BoundSet {
+				bind_point,
+				layout,
+				index: first_index + i as u32,
+				set: *set,
+			})
+			.collect::<Vec<_>>();
+		let changed = !bound_sets
+			.iter()
+			.all(|bound| self.descriptor_sets.contains(bound));
+		if changed {
+			self.descriptor_sets.retain(|bound| {
+				bound.bind_point != bind_point
+					|| (bound.layout == layout
+						&& !bound_sets.iter().any(|new| new.index == bound.index))
+			});
+			self.descriptor_sets.extend(bound_sets);
+		}
+		self.record(changed)
+	}
+
+	pub fn bind_vertex_buffers(
+		&mut self,
+		first_binding: u32,
+		buffers: &[(vk::Buffer, u64)],
+	) -> bool {
+		let first = first_binding as usize;
+		let changed = buffers.iter().enumerate().any(|(i, buffer)| {
+			self.vertex_buffers.get(first + i).cloned().flatten() != Some(*buffer)
+		});
+		if changed {
+			if self.vertex_buffers.len() < first + buffers.len() {
+				self.vertex_buffers.resize(first + buffers.len(), None);
+			}
+			for (i, buffer) in buffers.iter().enumerate() {
+				self.vertex_buffers[first + i] = Some(*buffer);
+			}
+		}
+		self.record(changed)
+	}
+
+	pub fn bind_index_buffer(
+		&mut self,
+		buffer: vk::Buffer,
+		offset: u64,
+		index_type: vk::IndexType,
+	) -> bool {
+		let binding = Some((buffer, offset, index_type));
+		let changed = self.index_buffer != binding;
+		self.index_buffer = binding;
+		self.record(changed)
+	}
+}
+
+#[cfg(test)]
+mod filtering {
+	use super::*;
+	use vk::Handle;
+
+	#[test]
+	fn rebinding_a_pipeline_is_filtered() {
+		let mut state = BindState::default();
+		let graphics = flags::PipelineBindPoint::GRAPHICS;
+		let compute = flags::PipelineBindPoint::COMPUTE;
+		assert!(state.bind_pipeline(graphics, vk::Pipeline::from_raw(1)));
+		assert!(!state.bind_pipeline(graphics, vk::Pipeline::from_raw(1)));
+		assert!(state.bind_pipeline(compute, vk::Pipeline::from_raw(1)));
+		assert!(state.bind_pipeline(graphics, vk::Pipeline::from_raw(2)));
+		assert_eq!(
+			state.statistics(),
+			BindStatistics {
+				emitted: 3,
+				filtered: 1
+			}
+		);
+		state.invalidate();
+		assert!(state.bind_pipeline(graphics, vk::Pipeline::from_raw(2)));
+	}
+
+	#[test]
+	fn descriptor_sets_are_tracked_per_index_and_layout() {
+		let mut state = BindState::default();
+		let graphics = flags::PipelineBindPoint::GRAPHICS;
+		let layout_a = vk::PipelineLayout::from_raw(1);
+		let layout_b = vk::PipelineLayout::from_raw(2);
+		let set = |raw| vk::DescriptorSet::from_raw(raw);
+		assert!(state.bind_descriptor_sets(graphics, layout_a, 0, &[set(10), set(11)]));
+		assert!(!state.bind_descriptor_sets(graphics, layout_a, 1, &[set(11)]));
+		assert!(state.bind_descriptor_sets(graphics, layout_a, 1, &[set(12)]));
+		assert!(!state.bind_descriptor_sets(graphics, layout_a, 0, &[set(10)]));
+		// A different layout may disturb the other sets.
+		assert!(state.bind_descriptor_sets(graphics, layout_b, 0, &[set(10)]));
+		assert!(state.bind_descriptor_sets(graphics, layout_a, 1, &[set(12)]));
+	}
+
+	#[test]
+	fn buffers_are_tracked_per_binding() {
+		let mut state = BindState::default();
+		let buffer = |raw| vk::Buffer::from_raw(raw);
+		assert!(state.bind_vertex_buffers(0, &[(buffer(1), 0), (buffer(2), 0)]));
+		assert!(!state.bind_vertex_buffers(1, &[(buffer(2), 0)]));
+		assert!(state.bind_vertex_buffers(1, &[(buffer(2), 16)]));
+		assert!(state.bind_vertex_buffers(3, &[(buffer(1), 0)]));
+		assert!(state.bind_index_buffer(buffer(3), 0, vk::IndexType::UINT32));
+		assert!(!state.bind_index_buffer(buffer(3), 0, vk::IndexType::UINT32));
+		assert!(state.bind_index_buffer(buffer(3), 0, vk::IndexType::UINT16));
+	}
+}
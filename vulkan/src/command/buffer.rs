@@ -3,20 +3,42 @@ use crate::{
 	command::{self, frame},
 	descriptor,
 	device::logical,
-	flags, image, pipeline, renderpass,
+	flags, image, pipeline, query, renderpass,
+	structs::subresource,
 	utility::{self, BoundObject, Viewport},
 };
-use std::sync::Arc;
+use enumset::EnumSet;
+use std::{ops::Range, sync::Arc};
 
 /// A ordered set of commands that will be executed on the GPU when they are submitted.
 /// You can get a command buffer from [`command::Pool::allocate_buffers`](command::Pool::allocate_buffers).
 pub struct Buffer {
 	recording_framebuffer: Option<backend::vk::Framebuffer>,
 	recording_render_pass: Option<backend::vk::RenderPass>,
+	recording_subpass: u32,
 	internal: backend::vk::CommandBuffer,
 	device: Arc<logical::Device>,
 	name: String,
 	bound_objects: Vec<BoundObject>,
+	ownership_transfers: Vec<command::PendingHalf>,
+	usage: flags::CommandBufferUsage,
+	resettable: bool,
+	active_queries: Vec<ActiveQuery>,
+	bound_dynamic_states: Option<Vec<flags::DynamicState>>,
+	bind_state: command::BindState,
+	label_depth: usize,
+	draw_label_color: Option<[f32; 4]>,
+	bound_graphics_pipeline: Option<Arc<pipeline::Pipeline>>,
+	recording_render_pass_name: Option<String>,
+}
+
+/// A query which has been begun but not yet ended,
+/// used to determine what secondary buffers must inherit.
+struct ActiveQuery {
+	pool: backend::vk::QueryPool,
+	index: u32,
+	kind: query::Kind,
+	precise: bool,
 }
 
 /// Internal only
@@ -25,6 +47,7 @@ impl Buffer {
 		device: Arc<logical::Device>,
 		name: String,
 		internal: backend::vk::CommandBuffer,
+		resettable: bool,
 	) -> Buffer {
 		Buffer {
 			device,
@@ -32,7 +55,18 @@ impl Buffer {
 			name,
 			recording_render_pass: None,
 			recording_framebuffer: None,
+			recording_subpass: 0,
 			bound_objects: Vec::new(),
+			ownership_transfers: Vec::new(),
+			usage: flags::CommandBufferUsage::empty(),
+			resettable,
+			active_queries: Vec::new(),
+			bound_dynamic_states: None,
+			bind_state: command::BindState::default(),
+			label_depth: 0,
+			draw_label_color: None,
+			bound_graphics_pipeline: None,
+			recording_render_pass_name: None,
 		}
 	}
 }
@@ -43,6 +77,12 @@ impl Buffer {
 	///
 	/// Must be called before all other methods.
 	///
+	/// If `primary` is provided, this buffer is a secondary buffer which inherits the render pass, subpass, and framebuffer
+	/// that the primary is currently recording (if any). Secondaries begun inside a render pass are automatically
+	/// given [`RENDER_PASS_CONTINUE`](flags::CommandBufferUsage::RENDER_PASS_CONTINUE).
+	/// Any occlusion or pipeline statistics queries which are active on the primary are also inherited
+	/// (inheriting an occlusion query requires the `inheritedQueries` device feature).
+	///
 	/// Equivalent to [`vkBeginCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkBeginCommandBuffer.html).
 	#[profiling::function]
 	pub fn begin(
@@ -50,61 +90,156 @@ impl Buffer {
 		usage: Option<flags::CommandBufferUsage>,
 		primary: Option<&command::Buffer>,
 	) -> utility::Result<()> {
+		self.usage = usage.unwrap_or(flags::CommandBufferUsage::empty());
 		let inheritance_info = match primary {
-			Some(primary_buffer) => backend::vk::CommandBufferInheritanceInfo::builder()
-				.render_pass(
-					primary_buffer
-						.recording_render_pass
-						.unwrap_or(backend::vk::RenderPass::null()),
-				)
-				.subpass(0)
-				.framebuffer(
-					primary_buffer
-						.recording_framebuffer
-						.unwrap_or(backend::vk::Framebuffer::null()),
-				)
-				.occlusion_query_enable(false)
-				.query_flags(backend::vk::QueryControlFlags::default())
-				.pipeline_statistics(backend::vk::QueryPipelineStatisticFlags::default()),
-			None => backend::vk::CommandBufferInheritanceInfo::builder(),
+			Some(primary_buffer) => {
+				self.recording_render_pass = primary_buffer.recording_render_pass;
+				self.recording_framebuffer = primary_buffer.recording_framebuffer;
+				self.recording_subpass = primary_buffer.recording_subpass;
+				self.recording_render_pass_name = primary_buffer.recording_render_pass_name.clone();
+				if self.recording_render_pass.is_some() {
+					self.usage |= flags::CommandBufferUsage::RENDER_PASS_CONTINUE;
+				}
+				backend::vk::CommandBufferInheritanceInfo::builder()
+					.render_pass(
+						self.recording_render_pass
+							.unwrap_or(backend::vk::RenderPass::null()),
+					)
+					.subpass(self.recording_subpass)
+					.framebuffer(
+						self.recording_framebuffer
+							.unwrap_or(backend::vk::Framebuffer::null()),
+					)
+					.occlusion_query_enable(primary_buffer.is_occlusion_query_active())
+					.query_flags(primary_buffer.inherited_query_flags())
+					.pipeline_statistics(primary_buffer.inherited_pipeline_statistics())
+			}
+			None => {
+				self.recording_render_pass = None;
+				self.recording_framebuffer = None;
+				self.recording_subpass = 0;
+				self.recording_render_pass_name = None;
+				backend::vk::CommandBufferInheritanceInfo::builder()
+			}
 		};
 		let info = backend::vk::CommandBufferBeginInfo::builder()
-			.flags(usage.unwrap_or(flags::CommandBufferUsage::empty()))
+			.flags(self.usage)
 			.inheritance_info(&inheritance_info);
 		self.bound_objects.clear();
+		self.ownership_transfers.clear();
+		self.active_queries.clear();
+		self.bound_dynamic_states = None;
+		self.bind_state.reset();
+		self.label_depth = 0;
+		self.bound_graphics_pipeline = None;
 		Ok(unsafe { self.device.begin_command_buffer(self.internal, &info) }?)
 	}
 
+	/// Returns the buffer to its initial state, releasing any resources it was keeping alive.
+	/// If `release_resources` is true, the memory used by the buffer is returned to its pool.
+	///
+	/// The buffer must have been allocated from a pool created with
+	/// [`RESET_COMMAND_BUFFER`](flags::CommandPoolCreate::RESET_COMMAND_BUFFER),
+	/// and must not be pending execution. Prefer [`Pool::reset`](command::Pool::reset)
+	/// when every buffer from the pool is being reset.
+	///
+	/// Equivalent to [`vkResetCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkResetCommandBuffer.html).
+	pub fn reset(&mut self, release_resources: bool) -> utility::Result<()> {
+		if !self.resettable {
+			return Err(utility::Error::InvalidCommandPool(format!(
+				"buffer {:?} cannot be reset individually, its pool was not created with RESET_COMMAND_BUFFER",
+				self.name
+			)));
+		}
+		let flags = match release_resources {
+			true => backend::vk::CommandBufferResetFlags::RELEASE_RESOURCES,
+			false => backend::vk::CommandBufferResetFlags::empty(),
+		};
+		unsafe { self.device.reset_command_buffer(self.internal, flags) }?;
+		self.bound_objects.clear();
+		self.ownership_transfers.clear();
+		self.usage = flags::CommandBufferUsage::empty();
+		self.active_queries.clear();
+		self.bound_dynamic_states = None;
+		self.bind_state.reset();
+		self.label_depth = 0;
+		self.bound_graphics_pipeline = None;
+		Ok(())
+	}
+
 	/// Finalizes the commands in the buffer.
 	///
 	/// Can only be called after [`begin`](Buffer::begin).
+	/// In debug builds, panics if any [`labels`](Buffer::begin_label) have not been ended.
 	///
 	/// Equivalent to [`vkEndCommandBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkEndCommandBuffer.html).
 	pub fn end(&self) -> utility::Result<()> {
+		debug_assert!(
+			self.label_depth == 0,
+			"{:?} was ended with {} debug label(s) which were never ended",
+			self.name,
+			self.label_depth
+		);
 		Ok(unsafe { self.device.end_command_buffer(self.internal) }?)
 	}
 
-	/// Executes the commands within secondary command buffers.
+	/// Executes the commands within secondary command buffers, in order.
+	///
+	/// The resources bound by the secondaries are merged into this buffer,
+	/// so they are kept alive for as long as this buffer's submission is.
+	/// Nothing is recorded if there are no secondaries.
 	///
 	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
 	///
 	/// Equivalent to [`vkCmdExecuteCommands`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdExecuteCommands.html).
-	pub fn execute(&self, secondary_buffers: Vec<&command::Buffer>) {
-		let unwraped = secondary_buffers
-			.iter()
-			.map(|cmd_buffer| ***cmd_buffer)
-			.collect::<Vec<_>>();
+	pub fn execute<'a>(
+		&mut self,
+		secondary_buffers: impl IntoIterator<Item = &'a command::Buffer>,
+	) {
+		let mut unwraped = Vec::new();
+		for secondary in secondary_buffers {
+			unwraped.push(secondary.internal);
+			self.bound_objects
+				.extend(secondary.bound_objects.iter().cloned());
+			self.ownership_transfers
+				.extend(secondary.ownership_transfers.iter().cloned());
+		}
+		if unwraped.is_empty() {
+			return;
+		}
 		unsafe { self.device.cmd_execute_commands(self.internal, &unwraped) };
+		// The state bound by the primary is undefined after executing secondaries.
+		self.bind_state.invalidate();
+		self.bound_dynamic_states = None;
 	}
 }
 
+/// Debug label operations
 impl Buffer {
+	/// Begins a debug label, which groups the commands recorded until [`end_label`](Buffer::end_label) in debugging tools.
+	/// Prefer [`label_scope`](Buffer::label_scope), which cannot be left unbalanced.
 	#[profiling::function]
-	pub fn begin_label<TStr>(&self, name: TStr, color: [f32; 4])
+	pub fn begin_label<TStr>(&mut self, name: TStr, color: [f32; 4])
 	where
 		TStr: Into<String>,
 	{
 		self.device.begin_command_label(&self, name, color);
+		self.label_depth += 1;
+	}
+
+	/// Begins a debug label, which is ended when the returned scope is dropped.
+	/// Commands are recorded through the scope while it is alive.
+	#[must_use = "the label is ended as soon as the scope is dropped"]
+	pub fn label_scope<TStr>(
+		&mut self,
+		name: TStr,
+		color: [f32; 4],
+	) -> command::LabelScope<'_, Self>
+	where
+		TStr: Into<String>,
+	{
+		self.begin_label(name, color);
+		command::LabelScope::new(self, |buffer| buffer)
 	}
 
 	#[profiling::function]
@@ -116,9 +251,42 @@ impl Buffer {
 	}
 
 	#[profiling::function]
-	pub fn end_label(&self) {
+	pub fn end_label(&mut self) {
+		debug_assert!(
+			self.label_depth > 0,
+			"{:?} ended a debug label which was never begun",
+			self.name
+		);
+		self.label_depth = self.label_depth.saturating_sub(1);
 		self.device.end_command_label(&self);
 	}
+
+	/// When `color` is provided, a label naming the bound graphics pipeline and the render pass
+	/// is inserted before every draw. Pass None to stop labeling draws.
+	///
+	/// The setting is kept when the buffer is begun again or reset.
+	pub fn set_draw_labels(&mut self, color: Option<[f32; 4]>) {
+		self.draw_label_color = color;
+	}
+
+	fn insert_draw_label(&self, kind: &str) {
+		if let Some(color) = self.draw_label_color {
+			let pipeline = self
+				.bound_graphics_pipeline
+				.as_ref()
+				.map_or("<no pipeline>", |pipeline| {
+					utility::NamedObject::name(&**pipeline).as_str()
+				});
+			let label = match self.recording_render_pass_name.as_ref() {
+				Some(render_pass) => format!(
+					"{} {} ({} subpass {})",
+					kind, pipeline, render_pass, self.recording_subpass
+				),
+				None => format!("{} {}", kind, pipeline),
+			};
+			self.insert_label(label, color);
+		}
+	}
 }
 
 /// Copying data operations!
@@ -129,38 +297,287 @@ impl Buffer {
 	///
 	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
 	///
-	/// Equivalent to [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPipelineBarrier.html).
+	/// Equivalent to [`vkCmdPipelineBarrier2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdPipelineBarrier2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPipelineBarrier.html).
 	pub fn mark_pipeline_barrier(&self, barrier: command::barrier::Pipeline) {
-		use command::barrier::Kind::*;
-		let mut memory_barriers: Vec<backend::vk::MemoryBarrier> = Vec::new();
-		let mut buffer_barriers: Vec<backend::vk::BufferMemoryBarrier> = Vec::new();
-		let mut image_barriers: Vec<backend::vk::ImageMemoryBarrier> = Vec::new();
-		for barrier_kind in barrier.kinds {
-			match barrier_kind {
-				Memory(info) => {
-					memory_barriers.push(info.into());
-				}
-				Buffer(info) => {
-					buffer_barriers.push(info.as_vk());
-				}
-				Image(info) => {
-					image_barriers.push(info.as_vk());
-				}
-			}
+		barrier.apply_to_tracked();
+		self.record_pipeline_barrier(barrier);
+	}
+
+	fn record_pipeline_barrier(&self, barrier: command::barrier::Pipeline) {
+		if let Some(synchronization2) = self.device.synchronization2() {
+			let barriers = barrier.as_vk2();
+			let info = barriers.dependency_info();
+			unsafe { synchronization2.cmd_pipeline_barrier2(self.internal, &info) };
+			return;
 		}
+
+		let barriers = barrier.as_vk();
+		let (src_stages, dst_stages) = barrier.legacy_stages();
 		unsafe {
 			self.device.cmd_pipeline_barrier(
 				self.internal,
-				barrier.src_stage.into(),
-				barrier.dst_stage.into(),
-				backend::vk::DependencyFlags::empty(),
-				&memory_barriers[..],
-				&buffer_barriers[..],
-				&image_barriers[..],
+				src_stages,
+				dst_stages,
+				barrier.dependency,
+				&barriers.memory[..],
+				&barriers.buffers[..],
+				&barriers.images[..],
 			)
 		};
 	}
 
+	/// Sets an event once the source half of a barrier has completed,
+	/// which is the first half of a barrier split by [`wait_events`](Buffer::wait_events).
+	/// Independent commands can be recorded between the two halves, and do not wait for the barrier.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdSetEvent2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetEvent2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdSetEvent`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetEvent.html)
+	/// (which only uses the source stages of the barrier).
+	pub fn set_event(&mut self, event: &Arc<command::Event>, barrier: &command::barrier::Pipeline) {
+		match self.device.synchronization2() {
+			Some(synchronization2) => {
+				let barriers = barrier.as_vk2();
+				let info = barriers.dependency_info();
+				unsafe { synchronization2.cmd_set_event2(self.internal, ***event, &info) };
+			}
+			None => {
+				let (src_stages, _) = barrier.legacy_stages();
+				unsafe {
+					self.device
+						.cmd_set_event(self.internal, ***event, src_stages)
+				};
+			}
+		}
+		self.bound_objects.push(event.clone());
+	}
+
+	/// Waits for events to be set, then applies their barriers,
+	/// which is the second half of barriers split by [`set_event`](Buffer::set_event).
+	/// Each barrier must be the same as the one the event was set with,
+	/// and tracked buffers and images are updated to its destination state here (as if [`marked`](Buffer::mark_pipeline_barrier)).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdWaitEvents2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdWaitEvents2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdWaitEvents`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdWaitEvents.html)
+	/// (which merges the stages and barriers of all events).
+	pub fn wait_events(&mut self, events: Vec<(&Arc<command::Event>, command::barrier::Pipeline)>) {
+		if events.is_empty() {
+			return;
+		}
+		for (_, barrier) in events.iter() {
+			barrier.apply_to_tracked();
+		}
+		let handles = events
+			.iter()
+			.map(|(event, _)| ****event)
+			.collect::<Vec<_>>();
+
+		match self.device.synchronization2() {
+			Some(synchronization2) => {
+				let barriers = events
+					.iter()
+					.map(|(_, barrier)| barrier.as_vk2())
+					.collect::<Vec<_>>();
+				let infos = barriers
+					.iter()
+					.map(|barriers| barriers.dependency_info().build())
+					.collect::<Vec<_>>();
+				unsafe {
+					synchronization2.cmd_wait_events2(self.internal, &handles[..], &infos[..])
+				};
+			}
+			None => {
+				let mut src_stages = backend::vk::PipelineStageFlags::empty();
+				let mut dst_stages = backend::vk::PipelineStageFlags::empty();
+				let mut barriers = command::barrier::Barriers::default();
+				for (_, barrier) in events.iter() {
+					let (src, dst) = barrier.legacy_stages();
+					src_stages |= src;
+					dst_stages |= dst;
+					let mut event_barriers = barrier.as_vk();
+					barriers.memory.append(&mut event_barriers.memory);
+					barriers.buffers.append(&mut event_barriers.buffers);
+					barriers.images.append(&mut event_barriers.images);
+				}
+				unsafe {
+					self.device.cmd_wait_events(
+						self.internal,
+						&handles[..],
+						src_stages,
+						dst_stages,
+						&barriers.memory[..],
+						&barriers.buffers[..],
+						&barriers.images[..],
+					)
+				};
+			}
+		}
+		for (event, _) in events.into_iter() {
+			self.bound_objects.push(event.clone());
+		}
+	}
+
+	/// Resets an event once the provided stages of previous commands have completed,
+	/// so that it can be [`set`](Buffer::set_event) again.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdResetEvent2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdResetEvent2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdResetEvent`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdResetEvent.html).
+	pub fn reset_event(&mut self, event: &Arc<command::Event>, stages: impl Into<flags::StageSet>) {
+		let stages = stages.into();
+		match self.device.synchronization2() {
+			Some(synchronization2) => unsafe {
+				synchronization2.cmd_reset_event2(self.internal, ***event, stages.as_vk2())
+			},
+			None => {
+				let stages = match stages.is_empty() {
+					true => backend::vk::PipelineStageFlags::TOP_OF_PIPE,
+					false => stages.as_legacy(),
+				};
+				unsafe { self.device.cmd_reset_event(self.internal, ***event, stages) };
+			}
+		}
+		self.bound_objects.push(event.clone());
+	}
+
+	/// Transitions all of the subresources of a [`state tracked`](image::Builder::with_state_tracking) image
+	/// into a layout for use by some stages and accesses.
+	/// See [`transition_all`](Buffer::transition_all) for details.
+	pub fn transition(
+		&mut self,
+		image: &Arc<image::Image>,
+		layout: flags::ImageLayout,
+		stages: impl Into<flags::StageSet>,
+		access: impl Into<flags::AccessSet>,
+	) -> utility::Result<()> {
+		self.transition_all(vec![command::barrier::Transition::image(
+			image, layout, stages, access,
+		)])
+	}
+
+	/// Transitions an [`access tracked`](buffer::Builder::with_access_tracking) buffer
+	/// for use by some stages and accesses.
+	/// See [`transition_all`](Buffer::transition_all) for details.
+	pub fn transition_buffer(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		stages: impl Into<flags::StageSet>,
+		access: impl Into<flags::AccessSet>,
+	) -> utility::Result<()> {
+		self.transition_all(vec![command::barrier::Transition::buffer(
+			buffer, stages, access,
+		)])
+	}
+
+	/// Computes the barriers required to move each resource from its tracked state into its next use,
+	/// and marks them in a single pipeline barrier. No barrier is marked if none of the resources require one
+	/// (e.g. when a resource is only read in the same layout it was last read in).
+	///
+	/// The tracked state is updated as commands are recorded, so it assumes that command buffers
+	/// are submitted in the order they are recorded. State changes that happen outside of barriers,
+	/// like the final layout of a render pass attachment, must be reported to the resource
+	/// (i.e. via [`set_tracked_state`](image::Image::set_tracked_state)).
+	///
+	/// Returns an error without recording anything if any of the resources do not track their state.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdPipelineBarrier2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdPipelineBarrier2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPipelineBarrier.html).
+	pub fn transition_all(
+		&mut self,
+		transitions: Vec<command::barrier::Transition>,
+	) -> utility::Result<()> {
+		for transition in transitions.iter() {
+			transition.validate()?;
+		}
+		let mut src_stages = flags::StageSet::empty();
+		let mut dst_stages = flags::StageSet::empty();
+		let mut kinds = Vec::new();
+		for transition in transitions.into_iter() {
+			let (stages, mut barriers) = transition.apply()?;
+			if !barriers.is_empty() {
+				src_stages |= stages;
+				dst_stages |= transition.stages();
+			}
+			kinds.append(&mut barriers);
+			self.bound_objects.push(transition.bound_object());
+		}
+		if kinds.is_empty() {
+			return Ok(());
+		}
+		let barrier = kinds.into_iter().fold(
+			command::barrier::Pipeline::new(src_stages, dst_stages),
+			|barrier, kind| barrier.with(kind),
+		);
+		self.record_pipeline_barrier(barrier);
+		Ok(())
+	}
+
+	/// Records the release half of an [`ownership transfer`](command::OwnershipTransfer).
+	/// The command buffer must be submitted to a queue of the source family,
+	/// and the submission must signal a semaphore that the acquire waits on.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdPipelineBarrier2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdPipelineBarrier2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPipelineBarrier.html).
+	pub fn release_ownership(&mut self, transfer: &command::OwnershipTransfer) {
+		self.record_ownership(transfer, command::Half::Release);
+	}
+
+	/// Records the acquire half of an [`ownership transfer`](command::OwnershipTransfer).
+	/// The command buffer must be submitted to a queue of the destination family,
+	/// after the release and waiting on a semaphore that the release signals.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdPipelineBarrier2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdPipelineBarrier2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdPipelineBarrier`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPipelineBarrier.html).
+	pub fn acquire_ownership(&mut self, transfer: &command::OwnershipTransfer) {
+		self.record_ownership(transfer, command::Half::Acquire);
+	}
+
+	fn record_ownership(&mut self, transfer: &command::OwnershipTransfer, half: command::Half) {
+		self.mark_pipeline_barrier(transfer.barrier(half));
+		self.bound_objects.append(&mut transfer.bound_objects());
+		self.ownership_transfers.push(transfer.half(half));
+	}
+
+	/// The number of bind commands which have been recorded or skipped since the buffer was begun.
+	///
+	/// [`bind_pipeline`](Buffer::bind_pipeline), [`bind_descriptors`](Buffer::bind_descriptors),
+	/// [`bind_vertex_buffers`](Buffer::bind_vertex_buffers), and [`bind_index_buffer`](Buffer::bind_index_buffer)
+	/// are skipped when they would bind exactly what is already bound.
+	pub fn bind_statistics(&self) -> command::BindStatistics {
+		self.bind_state.statistics()
+	}
+
+	/// The usage the buffer was last [`begun`](Buffer::begin) with.
+	pub fn usage(&self) -> flags::CommandBufferUsage {
+		self.usage
+	}
+
+	pub(crate) fn bound_objects(&self) -> &Vec<BoundObject> {
+		&self.bound_objects
+	}
+
+	pub(crate) fn ownership_transfers(&self) -> &Vec<command::PendingHalf> {
+		&self.ownership_transfers
+	}
+
 	/// Copies data from some buffer to an image for a set region of the buffer.
 	///
 	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
@@ -197,6 +614,48 @@ impl Buffer {
 		}
 	}
 
+	/// Copies data from an area of an image to some buffer for a set region of the image.
+	///
+	/// The image must have [`TRANSFER_SRC`](flags::ImageUsage::TRANSFER_SRC) usage
+	/// and the buffer must have [`TRANSFER_DST`](flags::BufferUsage::TRANSFER_DST) usage.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdCopyImageToBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdCopyImageToBuffer.html).
+	pub fn copy_image_to_buffer(
+		&self,
+		image: &image::Image,
+		layout: flags::ImageLayout,
+		buffer: &buffer::Buffer,
+		regions: Vec<command::CopyImageToBuffer>,
+	) -> utility::Result<()> {
+		image.require_usage(flags::ImageUsage::TRANSFER_SRC, "copy source")?;
+		buffer.require_usage(flags::BufferUsage::TRANSFER_DST, "copy destination")?;
+		let regions = regions
+			.into_iter()
+			.map(|region| {
+				backend::vk::BufferImageCopy::builder()
+					.buffer_offset(region.buffer_offset as u64)
+					.buffer_row_length(0)
+					.buffer_image_height(0)
+					.image_subresource(region.layers.into())
+					.image_offset(region.offset)
+					.image_extent(region.size)
+					.build()
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			self.device.cmd_copy_image_to_buffer(
+				self.internal,
+				**image,
+				layout.into(),
+				**buffer,
+				&regions[..],
+			);
+		}
+		Ok(())
+	}
+
 	/// Copies some data from one buffer to another.
 	///
 	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
@@ -223,6 +682,283 @@ impl Buffer {
 				.cmd_copy_buffer(self.internal, **src, **dst, &regions[..]);
 		}
 	}
+
+	/// Fills a range of a buffer with repeated copies of `data`.
+	/// An unbounded end fills the rest of the buffer, rounded down to a multiple of 4 bytes.
+	///
+	/// The buffer must have [`TRANSFER_DST`](flags::BufferUsage::TRANSFER_DST) usage,
+	/// and the range must start and end on multiples of 4 bytes.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdFillBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdFillBuffer.html).
+	pub fn fill_buffer(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		range: impl std::ops::RangeBounds<usize>,
+		data: u32,
+	) -> utility::Result<()> {
+		use utility::NamedObject;
+		buffer.require_usage(flags::BufferUsage::TRANSFER_DST, "filling")?;
+		let (offset, size) = command::fill_range(range).map_err(|err| {
+			utility::Error::InvalidBufferUsage(format!(
+				"{:?} cannot be filled by {:?}: {}",
+				buffer.name(),
+				self.name,
+				err
+			))
+		})?;
+		self.validate_buffer_write(buffer, offset, size, None)?;
+		unsafe {
+			self.device.cmd_fill_buffer(
+				self.internal,
+				***buffer,
+				offset as u64,
+				size.map_or(backend::vk::WHOLE_SIZE, |size| size as u64),
+				data,
+			)
+		};
+		self.bound_objects.push(buffer.clone());
+		Ok(())
+	}
+
+	/// Writes `data` into a buffer at `offset`, with the data being stored inline in the command buffer.
+	/// This avoids a staging buffer for small writes, like patching a few values or resetting a counter.
+	///
+	/// The buffer must have [`TRANSFER_DST`](flags::BufferUsage::TRANSFER_DST) usage,
+	/// `offset` and the size of `T` must be multiples of 4, and `T` can be at most
+	/// [`MAX_UPDATE_BUFFER_SIZE`](command::MAX_UPDATE_BUFFER_SIZE) bytes.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdUpdateBuffer`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdUpdateBuffer.html).
+	pub fn update_buffer<T>(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		offset: usize,
+		data: &T,
+	) -> utility::Result<()>
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		self.update_buffer_bytes(buffer, offset, bytemuck::bytes_of(data))
+	}
+
+	/// See [`update_buffer`](Buffer::update_buffer).
+	pub(crate) fn update_buffer_bytes(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		offset: usize,
+		bytes: &[u8],
+	) -> utility::Result<()> {
+		buffer.require_usage(flags::BufferUsage::TRANSFER_DST, "updating")?;
+		self.validate_buffer_write(
+			buffer,
+			offset,
+			Some(bytes.len()),
+			Some(command::MAX_UPDATE_BUFFER_SIZE),
+		)?;
+		unsafe {
+			self.device
+				.cmd_update_buffer(self.internal, ***buffer, offset as u64, bytes)
+		};
+		self.bound_objects.push(buffer.clone());
+		Ok(())
+	}
+
+	fn validate_buffer_write(
+		&self,
+		buffer: &buffer::Buffer,
+		offset: usize,
+		size: Option<usize>,
+		limit: Option<usize>,
+	) -> utility::Result<()> {
+		use utility::NamedObject;
+		command::validate_buffer_write(buffer.size(), offset, size, limit).map_err(|err| {
+			utility::Error::InvalidBufferUsage(format!(
+				"{:?} cannot be written by {:?}: {}",
+				buffer.name(),
+				self.name,
+				err
+			))
+		})
+	}
+
+	/// Copies areas of one image to another image without any scaling or format conversion.
+	///
+	/// The source image must have [`TRANSFER_SRC`](flags::ImageUsage::TRANSFER_SRC) usage
+	/// and the destination image must have [`TRANSFER_DST`](flags::ImageUsage::TRANSFER_DST) usage.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdCopyImage`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdCopyImage.html).
+	pub fn copy_image_to_image(
+		&self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	) -> utility::Result<()> {
+		src.require_usage(flags::ImageUsage::TRANSFER_SRC, "copy source")?;
+		dst.require_usage(flags::ImageUsage::TRANSFER_DST, "copy destination")?;
+		let regions = regions
+			.into_iter()
+			.map(|region| {
+				backend::vk::ImageCopy::builder()
+					.src_subresource(region.src_layers.into())
+					.src_offset(region.src_offset)
+					.dst_subresource(region.dst_layers.into())
+					.dst_offset(region.dst_offset)
+					.extent(region.size)
+					.build()
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			self.device.cmd_copy_image(
+				self.internal,
+				**src,
+				src_layout.into(),
+				**dst,
+				dst_layout.into(),
+				&regions[..],
+			);
+		}
+		Ok(())
+	}
+
+	/// Copies areas of one image to another image, scaling and converting the format as needed.
+	/// The filter determines how texels are sampled when the areas are different sizes.
+	///
+	/// The source image must have [`TRANSFER_SRC`](flags::ImageUsage::TRANSFER_SRC) usage
+	/// and the destination image must have [`TRANSFER_DST`](flags::ImageUsage::TRANSFER_DST) usage.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdBlitImage`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdBlitImage.html).
+	pub fn blit_image(
+		&self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::BlitImage>,
+		filter: flags::Filter,
+	) -> utility::Result<()> {
+		src.require_usage(flags::ImageUsage::TRANSFER_SRC, "blit source")?;
+		dst.require_usage(flags::ImageUsage::TRANSFER_DST, "blit destination")?;
+		let regions = regions
+			.into_iter()
+			.map(|region| {
+				backend::vk::ImageBlit::builder()
+					.src_subresource(region.src_layers.into())
+					.src_offsets(region.src_bounds)
+					.dst_subresource(region.dst_layers.into())
+					.dst_offsets(region.dst_bounds)
+					.build()
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			self.device.cmd_blit_image(
+				self.internal,
+				**src,
+				src_layout.into(),
+				**dst,
+				dst_layout.into(),
+				&regions[..],
+				filter,
+			);
+		}
+		Ok(())
+	}
+
+	/// Resolves areas of a multisampled image into a single-sampled image.
+	///
+	/// The source image must have [`TRANSFER_SRC`](flags::ImageUsage::TRANSFER_SRC) usage
+	/// and the destination image must have [`TRANSFER_DST`](flags::ImageUsage::TRANSFER_DST) usage.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdResolveImage`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdResolveImage.html).
+	pub fn resolve_image(
+		&self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	) -> utility::Result<()> {
+		src.require_usage(flags::ImageUsage::TRANSFER_SRC, "resolve source")?;
+		dst.require_usage(flags::ImageUsage::TRANSFER_DST, "resolve destination")?;
+		let regions = regions
+			.into_iter()
+			.map(|region| {
+				backend::vk::ImageResolve::builder()
+					.src_subresource(region.src_layers.into())
+					.src_offset(region.src_offset)
+					.dst_subresource(region.dst_layers.into())
+					.dst_offset(region.dst_offset)
+					.extent(region.size)
+					.build()
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			self.device.cmd_resolve_image(
+				self.internal,
+				**src,
+				src_layout.into(),
+				**dst,
+				dst_layout.into(),
+				&regions[..],
+			);
+		}
+		Ok(())
+	}
+
+	/// Clears ranges of an image to a single color, or depth and stencil value.
+	///
+	/// The image must have [`TRANSFER_DST`](flags::ImageUsage::TRANSFER_DST) usage,
+	/// and must be in the [`General`](flags::ImageLayout::General)
+	/// or [`TransferDstOptimal`](flags::ImageLayout::TransferDstOptimal) layout.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end), outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdClearColorImage`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdClearColorImage.html)
+	/// or [`vkCmdClearDepthStencilImage`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdClearDepthStencilImage.html).
+	pub fn clear_image(
+		&self,
+		image: &image::Image,
+		layout: flags::ImageLayout,
+		value: renderpass::ClearValue,
+		ranges: Vec<subresource::Range>,
+	) -> utility::Result<()> {
+		image.require_usage(flags::ImageUsage::TRANSFER_DST, "clearing")?;
+		let ranges = ranges
+			.into_iter()
+			.map(|range| range.into())
+			.collect::<Vec<backend::vk::ImageSubresourceRange>>();
+		match value {
+			renderpass::ClearValue::Color(color) => unsafe {
+				self.device.cmd_clear_color_image(
+					self.internal,
+					**image,
+					layout.into(),
+					&backend::vk::ClearColorValue { float32: color },
+					&ranges[..],
+				);
+			},
+			renderpass::ClearValue::DepthStencil(depth, stencil) => unsafe {
+				self.device.cmd_clear_depth_stencil_image(
+					self.internal,
+					**image,
+					layout.into(),
+					&backend::vk::ClearDepthStencilValue { depth, stencil },
+					&ranges[..],
+				);
+			},
+		}
+		Ok(())
+	}
 }
 
 /// Render Pass operations
@@ -264,6 +1000,8 @@ impl Buffer {
 		};
 		self.recording_render_pass = Some(**render_pass);
 		self.recording_framebuffer = Some(***frame_buffer);
+		self.recording_subpass = 0;
+		self.recording_render_pass_name = Some(utility::NamedObject::name(render_pass).clone());
 		self.bound_objects.push(frame_buffer.clone());
 	}
 
@@ -284,26 +1022,82 @@ impl Buffer {
 				},
 			)
 		};
+		self.recording_subpass += 1;
 	}
 
-	/// Stops the render pass for recording rendering instructions.
+	/// Clears areas of attachments in the current subpass.
+	/// Returns an error if the layers of any rect are empty or inverted.
 	///
-	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`end`](Buffer::end).
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
 	///
-	/// Equivalent to [`vkCmdEndRenderPass`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdEndRenderPass.html).
-	#[profiling::function]
-	pub fn stop_render_pass(&mut self) {
-		unsafe { self.device.cmd_end_render_pass(self.internal) };
-		self.recording_render_pass = None;
-		self.recording_framebuffer = None;
-	}
-}
-
+	/// Equivalent to [`vkCmdClearAttachments`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdClearAttachments.html).
+	pub fn clear_attachments(
+		&self,
+		attachments: Vec<command::ClearAttachment>,
+		rects: Vec<command::ClearRect>,
+	) -> utility::Result<()> {
+		let rects = rects
+			.into_iter()
+			.map(|rect| {
+				let layer_count = rect.layer_count().map_err(|err| {
+					utility::Error::InvalidClearRegion(format!(
+						"{:?} cannot clear attachments: {}",
+						self.name, err
+					))
+				})?;
+				Ok(backend::vk::ClearRect::builder()
+					.rect(rect.area)
+					.base_array_layer(rect.layers.start)
+					.layer_count(layer_count)
+					.build())
+			})
+			.collect::<utility::Result<Vec<_>>>()?;
+		let attachments = attachments
+			.into_iter()
+			.map(|attachment| match attachment {
+				command::ClearAttachment::Color(index, color) => {
+					backend::vk::ClearAttachment::builder()
+						.aspect_mask(flags::ImageAspect::COLOR)
+						.color_attachment(index as u32)
+						.clear_value(renderpass::ClearValue::Color(color).into())
+						.build()
+				}
+				command::ClearAttachment::DepthStencil(aspect, depth, stencil) => {
+					backend::vk::ClearAttachment::builder()
+						.aspect_mask(aspect)
+						.clear_value(renderpass::ClearValue::DepthStencil(depth, stencil).into())
+						.build()
+				}
+			})
+			.collect::<Vec<_>>();
+		unsafe {
+			self.device
+				.cmd_clear_attachments(self.internal, &attachments[..], &rects[..])
+		};
+		Ok(())
+	}
+
+	/// Stops the render pass for recording rendering instructions.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdEndRenderPass`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdEndRenderPass.html).
+	#[profiling::function]
+	pub fn stop_render_pass(&mut self) {
+		unsafe { self.device.cmd_end_render_pass(self.internal) };
+		self.recording_render_pass = None;
+		self.recording_framebuffer = None;
+		self.recording_subpass = 0;
+		self.recording_render_pass_name = None;
+	}
+}
+
 /// Pipeline operations
 impl Buffer {
 	/// Binds a pipeline to the buffer. The pipeline will stay bound until another pipeline is bound.
 	///
-	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	/// Graphics pipelines can only be bound after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	/// Compute pipelines can be bound at any point after [`begin`](Buffer::begin), inside or outside of a render pass.
 	///
 	/// Equivalent to [`vkCmdBindPipeline`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdBindPipeline.html).
 	#[profiling::function]
@@ -312,17 +1106,28 @@ impl Buffer {
 		pipeline: &Arc<pipeline::Pipeline>,
 		bind_point: flags::PipelineBindPoint,
 	) {
+		if bind_point == flags::PipelineBindPoint::GRAPHICS {
+			self.bound_graphics_pipeline = Some(pipeline.clone());
+		}
+		if !self.bind_state.bind_pipeline(bind_point, ***pipeline) {
+			return;
+		}
 		unsafe {
 			self.device
 				.cmd_bind_pipeline(self.internal, bind_point, ***pipeline)
 		};
+		if bind_point == flags::PipelineBindPoint::GRAPHICS {
+			self.bound_dynamic_states = Some(pipeline.dynamic_states().clone());
+		}
 		self.bound_objects.push(pipeline.clone());
 	}
 
 	/// Binds descriptors for a given pipeline layout.
 	/// The descriptors will stay bound until another pipeline is bound via [`bind_pipeline`](Buffer::bind_pipeline).
 	///
-	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	/// Descriptors for the graphics bind point can only be bound after [`start_render_pass`](Buffer::start_render_pass)
+	/// and before [`stop_render_pass`](Buffer::stop_render_pass).
+	/// Descriptors for the compute bind point can be bound at any point after [`begin`](Buffer::begin), inside or outside of a render pass.
 	///
 	/// If used, this should only be called after [`bind_pipeline`](Buffer::bind_pipeline).
 	///
@@ -336,6 +1141,14 @@ impl Buffer {
 		sets: Vec<&descriptor::Set>,
 	) {
 		let vk_sets = sets.iter().map(|set| ***set).collect::<Vec<_>>();
+		if !self.bind_state.bind_descriptor_sets(
+			bind_point,
+			**layout,
+			first_set_index as u32,
+			&vk_sets[..],
+		) {
+			return;
+		}
 		let offsets = Vec::new();
 		unsafe {
 			self.device.cmd_bind_descriptor_sets(
@@ -352,8 +1165,13 @@ impl Buffer {
 		}
 	}
 
+	/// Sets the viewports, starting at index `first`, that should be used for pipelines which use the [`DynamicState.VIEWPORT`](flags::DynamicState::VIEWPORT) flag.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
 	/// Equivalent to [`vkCmdSetViewport`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetViewport.html).
 	pub fn set_dynamic_viewport(&self, first: usize, viewports: Vec<Viewport>) {
+		self.debug_check_dynamic(flags::DynamicState::VIEWPORT);
 		let vk = viewports
 			.into_iter()
 			.map(|viewport| viewport.into())
@@ -364,37 +1182,91 @@ impl Buffer {
 		};
 	}
 
-	/// Sets the scissor that should be used for pipelines which use the [`DynamicState.SCISSOR`](flags::DynamicState::SCISSOR) flag.
+	/// Sets the scissors, starting at index `first`, that should be used for pipelines which use the [`DynamicState.SCISSOR`](flags::DynamicState::SCISSOR) flag.
 	///
-	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
 	///
 	/// Equivalent to [`vkCmdSetScissor`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetScissor.html).
-	pub fn set_dynamic_scissors(&self, scissors: Vec<utility::Scissor>) {
+	pub fn set_dynamic_scissors(&self, first: usize, scissors: Vec<utility::Scissor>) {
+		self.debug_check_dynamic(flags::DynamicState::SCISSOR);
 		let scissors = scissors
 			.into_iter()
 			.map(|scissor| scissor.into())
 			.collect::<Vec<_>>();
-		unsafe { self.device.cmd_set_scissor(self.internal, 0, &scissors[..]) };
+		unsafe {
+			self.device
+				.cmd_set_scissor(self.internal, first as u32, &scissors[..])
+		};
 	}
 
+	/// Writes `data` into the push constants of `layout` at `offset`, for the shader `stages`.
+	///
+	/// In debug builds, panics if the bytes are not covered by the layout's [`ranges`](pipeline::PushConstantRange)
+	/// for all of the stages (see [`Layout::validate_push_constant`](pipeline::layout::Layout::validate_push_constant)).
+	/// Use [`push_constants`](Buffer::push_constants) to validate once up front instead.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdPushConstants`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPushConstants.html).
 	pub fn push_constant<T>(
 		&self,
 		layout: &pipeline::layout::Layout,
-		stage: flags::ShaderKind,
+		stages: impl Into<EnumSet<flags::ShaderKind>>,
 		offset: usize,
 		data: &T,
 	) where
 		T: Sized + bytemuck::Pod,
 	{
-		use bytemuck::bytes_of;
-		// TODO: add layout validation to prevent writing over push constant boundaries?
+		self.push_constant_bytes(layout, stages.into(), offset, bytemuck::bytes_of(data));
+	}
+
+	/// See [`push_constant`](Buffer::push_constant).
+	pub(crate) fn push_constant_bytes(
+		&self,
+		layout: &pipeline::layout::Layout,
+		stages: EnumSet<flags::ShaderKind>,
+		offset: usize,
+		bytes: &[u8],
+	) {
+		#[cfg(debug_assertions)]
+		if let Err(err) = layout.validate_push_constant(stages, offset, bytes.len()) {
+			panic!("Cannot push constants in {:?}: {}", self.name, err);
+		}
+		self.record_push_constants(**layout, stages, offset, bytes);
+	}
+
+	/// Writes `data` into a block of push constants which was validated when it was
+	/// [`created`](pipeline::layout::Layout::push_constants).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdPushConstants`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdPushConstants.html).
+	pub fn push_constants<T>(&self, block: &pipeline::layout::PushConstants<'_, T>, data: &T)
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		self.record_push_constants(
+			**block.layout(),
+			block.stages(),
+			block.offset(),
+			bytemuck::bytes_of(data),
+		);
+	}
+
+	fn record_push_constants(
+		&self,
+		layout: backend::vk::PipelineLayout,
+		stages: EnumSet<flags::ShaderKind>,
+		offset: usize,
+		bytes: &[u8],
+	) {
 		unsafe {
 			self.device.cmd_push_constants(
 				self.internal,
-				**layout,
-				stage.into(),
+				layout,
+				flags::ShaderKind::fold(&stages),
 				offset as u32,
-				bytes_of(data),
+				bytes,
 			)
 		};
 	}
@@ -416,10 +1288,19 @@ impl Buffer {
 		buffers: Vec<&Arc<buffer::Buffer>>,
 		offsets: Vec<u64>,
 	) {
-		use std::ops::Deref;
-		let mut vk_buffers = Vec::with_capacity(buffers.len());
+		let vk_buffers = buffers.iter().map(|arc| ****arc).collect::<Vec<_>>();
+		let bindings = vk_buffers
+			.iter()
+			.cloned()
+			.zip(offsets.iter().cloned())
+			.collect::<Vec<_>>();
+		if !self
+			.bind_state
+			.bind_vertex_buffers(binding_index, &bindings[..])
+		{
+			return;
+		}
 		for arc in buffers.into_iter() {
-			vk_buffers.push(*arc.deref().deref());
 			self.bound_objects.push(arc.clone());
 		}
 		unsafe {
@@ -443,6 +1324,12 @@ impl Buffer {
 		use std::ops::Deref;
 		assert_ne!(*buffer.index_type(), None);
 		let index_type = buffer.index_type().unwrap();
+		if !self
+			.bind_state
+			.bind_index_buffer(*buffer.deref().deref(), offset, index_type)
+		{
+			return;
+		}
 		self.bound_objects.push(buffer.clone());
 		unsafe {
 			self.device.cmd_bind_index_buffer(
@@ -474,6 +1361,7 @@ impl Buffer {
 		first_instance: usize,
 		vertex_offset: usize,
 	) {
+		self.insert_draw_label("DrawIndexed");
 		unsafe {
 			self.device.cmd_draw_indexed(
 				self.internal,
@@ -485,6 +1373,629 @@ impl Buffer {
 			)
 		};
 	}
+
+	/// Draws primitives from the bound vertex buffers, without an index buffer.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	///
+	/// Equivalent to [`vkCmdDraw`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDraw.html).
+	pub fn draw_vertices(
+		&self,
+		vertex_count: usize,
+		first_vertex: usize,
+		instance_count: usize,
+		first_instance: usize,
+	) {
+		self.insert_draw_label("Draw");
+		unsafe {
+			self.device.cmd_draw(
+				self.internal,
+				vertex_count as u32,
+				instance_count as u32,
+				first_vertex as u32,
+				first_instance as u32,
+			)
+		};
+	}
+
+	/// Draws `draw_count` times, reading each [`DrawIndirectCommand`](command::DrawIndirectCommand) from a buffer.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	///
+	/// Equivalent to [`vkCmdDrawIndirect`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDrawIndirect.html).
+	pub fn draw_indirect(
+		&mut self,
+		draws: command::IndirectDraws,
+		draw_count: usize,
+	) -> utility::Result<()> {
+		draws.validate(
+			std::mem::size_of::<command::DrawIndirectCommand>(),
+			draw_count,
+			"indirect draws",
+		)?;
+		self.insert_draw_label("DrawIndirect");
+		unsafe {
+			self.device.cmd_draw_indirect(
+				self.internal,
+				***draws.buffer,
+				draws.offset as u64,
+				draw_count as u32,
+				draws.stride as u32,
+			)
+		};
+		self.bound_objects.push(draws.buffer.clone());
+		Ok(())
+	}
+
+	/// Draws `draw_count` times, reading each [`DrawIndexedIndirectCommand`](command::DrawIndexedIndirectCommand) from a buffer.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	///
+	/// Equivalent to [`vkCmdDrawIndexedIndirect`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDrawIndexedIndirect.html).
+	pub fn draw_indexed_indirect(
+		&mut self,
+		draws: command::IndirectDraws,
+		draw_count: usize,
+	) -> utility::Result<()> {
+		draws.validate(
+			std::mem::size_of::<command::DrawIndexedIndirectCommand>(),
+			draw_count,
+			"indexed indirect draws",
+		)?;
+		self.insert_draw_label("DrawIndexedIndirect");
+		unsafe {
+			self.device.cmd_draw_indexed_indirect(
+				self.internal,
+				***draws.buffer,
+				draws.offset as u64,
+				draw_count as u32,
+				draws.stride as u32,
+			)
+		};
+		self.bound_objects.push(draws.buffer.clone());
+		Ok(())
+	}
+
+	/// Returns the extension for indirect count draws,
+	/// after validating that the draw count can be read from `count_buffer` at `count_offset`.
+	fn validate_indirect_count(
+		&self,
+		count_buffer: &Arc<buffer::Buffer>,
+		count_offset: usize,
+	) -> utility::Result<&backend::extensions::khr::DrawIndirectCount> {
+		let extension = self.device.draw_indirect_count().ok_or_else(|| {
+			utility::Error::FeatureNotEnabled("VK_KHR_draw_indirect_count".to_owned())
+		})?;
+		count_buffer.require_usage(flags::BufferUsage::INDIRECT_BUFFER, "draw counts")?;
+		if !count_offset.is_multiple_of(4) || count_offset + 4 > count_buffer.size() {
+			use utility::NamedObject;
+			return Err(utility::Error::InvalidBufferUsage(format!(
+				"{:?} cannot be used for draw counts: offset {} must be a multiple of 4 and leave room for a u32",
+				count_buffer.name(),
+				count_offset
+			)));
+		}
+		Ok(extension)
+	}
+
+	/// Draws up to `max_draw_count` times, reading each [`DrawIndirectCommand`](command::DrawIndirectCommand) from a buffer,
+	/// and the number of draws as a `u32` from `count_buffer` at `count_offset`.
+	///
+	/// Requires [`draw indirect count`](logical::Info::with_draw_indirect_count) to be enabled.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	///
+	/// Equivalent to [`vkCmdDrawIndirectCount`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDrawIndirectCount.html).
+	pub fn draw_indirect_count(
+		&mut self,
+		draws: command::IndirectDraws,
+		count_buffer: &Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	) -> utility::Result<()> {
+		draws.validate(
+			std::mem::size_of::<command::DrawIndirectCommand>(),
+			max_draw_count,
+			"indirect draws",
+		)?;
+		let extension = self.validate_indirect_count(count_buffer, count_offset)?;
+		self.insert_draw_label("DrawIndirectCount");
+		unsafe {
+			extension.cmd_draw_indirect_count(
+				self.internal,
+				***draws.buffer,
+				draws.offset as u64,
+				***count_buffer,
+				count_offset as u64,
+				max_draw_count as u32,
+				draws.stride as u32,
+			)
+		};
+		self.bound_objects.push(draws.buffer.clone());
+		self.bound_objects.push(count_buffer.clone());
+		Ok(())
+	}
+
+	/// Draws up to `max_draw_count` times, reading each [`DrawIndexedIndirectCommand`](command::DrawIndexedIndirectCommand) from a buffer,
+	/// and the number of draws as a `u32` from `count_buffer` at `count_offset`.
+	///
+	/// Requires [`draw indirect count`](logical::Info::with_draw_indirect_count) to be enabled.
+	///
+	/// Can only be called after [`start_render_pass`](Buffer::start_render_pass) and before [`stop_render_pass`](Buffer::stop_render_pass).
+	///
+	/// Equivalent to [`vkCmdDrawIndexedIndirectCount`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDrawIndexedIndirectCount.html).
+	pub fn draw_indexed_indirect_count(
+		&mut self,
+		draws: command::IndirectDraws,
+		count_buffer: &Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	) -> utility::Result<()> {
+		draws.validate(
+			std::mem::size_of::<command::DrawIndexedIndirectCommand>(),
+			max_draw_count,
+			"indexed indirect draws",
+		)?;
+		let extension = self.validate_indirect_count(count_buffer, count_offset)?;
+		self.insert_draw_label("DrawIndexedIndirectCount");
+		unsafe {
+			extension.cmd_draw_indexed_indirect_count(
+				self.internal,
+				***draws.buffer,
+				draws.offset as u64,
+				***count_buffer,
+				count_offset as u64,
+				max_draw_count as u32,
+				draws.stride as u32,
+			)
+		};
+		self.bound_objects.push(draws.buffer.clone());
+		self.bound_objects.push(count_buffer.clone());
+		Ok(())
+	}
+}
+
+/// Compute operations
+impl Buffer {
+	/// Dispatches `group_counts` workgroups of the bound compute pipeline in each dimension.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdDispatch`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDispatch.html).
+	pub fn dispatch(&self, group_counts: [u32; 3]) {
+		unsafe {
+			self.device.cmd_dispatch(
+				self.internal,
+				group_counts[0],
+				group_counts[1],
+				group_counts[2],
+			)
+		};
+	}
+
+	/// Dispatches workgroups of the bound compute pipeline,
+	/// reading the [`DispatchIndirectCommand`](command::DispatchIndirectCommand) from a buffer at `offset`.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdDispatchIndirect`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDispatchIndirect.html).
+	pub fn dispatch_indirect(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		offset: usize,
+	) -> utility::Result<()> {
+		buffer.require_usage(flags::BufferUsage::INDIRECT_BUFFER, "indirect dispatches")?;
+		let end = offset + std::mem::size_of::<command::DispatchIndirectCommand>();
+		if !offset.is_multiple_of(4) || end > buffer.size() {
+			use utility::NamedObject;
+			return Err(utility::Error::InvalidBufferUsage(format!(
+				"{:?} cannot be used for indirect dispatches: offset {} must be a multiple of 4 and leave room for the command",
+				buffer.name(),
+				offset
+			)));
+		}
+		unsafe {
+			self.device
+				.cmd_dispatch_indirect(self.internal, ***buffer, offset as u64)
+		};
+		self.bound_objects.push(buffer.clone());
+		Ok(())
+	}
+
+	/// Dispatches `group_counts` workgroups of the bound compute pipeline in each dimension,
+	/// with the workgroup ids starting at `base_group` instead of zero.
+	/// The pipeline must have been built [`with_dispatch_base`](pipeline::ComputeBuilder::with_dispatch_base).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdDispatchBase`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdDispatchBase.html).
+	pub fn dispatch_base(&self, base_group: [u32; 3], group_counts: [u32; 3]) {
+		unsafe {
+			self.device.cmd_dispatch_base(
+				self.internal,
+				base_group[0],
+				base_group[1],
+				base_group[2],
+				group_counts[0],
+				group_counts[1],
+				group_counts[2],
+			)
+		};
+	}
+}
+
+/// Dynamic state operations.
+///
+/// Each state can only be set for pipelines which declared it as [`dynamic`](pipeline::state::Dynamic).
+/// In debug builds, setting a state which the currently bound graphics pipeline did not declare is a panic.
+/// States can be set before any pipeline is bound, and stay set across pipelines which declare them as dynamic.
+impl Buffer {
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetLineWidth`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetLineWidth.html).
+	pub fn set_dynamic_line_width(&self, width: f32) {
+		self.debug_check_dynamic(flags::DynamicState::LINE_WIDTH);
+		unsafe { self.device.cmd_set_line_width(self.internal, width) };
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthBias`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetDepthBias.html).
+	pub fn set_dynamic_depth_bias(&self, constant_factor: f32, clamp: f32, slope_factor: f32) {
+		self.debug_check_dynamic(flags::DynamicState::DEPTH_BIAS);
+		unsafe {
+			self.device
+				.cmd_set_depth_bias(self.internal, constant_factor, clamp, slope_factor)
+		};
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetBlendConstants`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetBlendConstants.html).
+	pub fn set_dynamic_blend_constants(&self, constants: [f32; 4]) {
+		self.debug_check_dynamic(flags::DynamicState::BLEND_CONSTANTS);
+		unsafe {
+			self.device
+				.cmd_set_blend_constants(self.internal, &constants)
+		};
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthBounds`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetDepthBounds.html).
+	pub fn set_dynamic_depth_bounds(&self, min: f32, max: f32) {
+		self.debug_check_dynamic(flags::DynamicState::DEPTH_BOUNDS);
+		unsafe { self.device.cmd_set_depth_bounds(self.internal, min, max) };
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetStencilCompareMask`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetStencilCompareMask.html).
+	pub fn set_dynamic_stencil_compare_mask(&self, faces: flags::StencilFace, mask: u32) {
+		self.debug_check_dynamic(flags::DynamicState::STENCIL_COMPARE_MASK);
+		unsafe {
+			self.device
+				.cmd_set_stencil_compare_mask(self.internal, faces, mask)
+		};
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetStencilWriteMask`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetStencilWriteMask.html).
+	pub fn set_dynamic_stencil_write_mask(&self, faces: flags::StencilFace, mask: u32) {
+		self.debug_check_dynamic(flags::DynamicState::STENCIL_WRITE_MASK);
+		unsafe {
+			self.device
+				.cmd_set_stencil_write_mask(self.internal, faces, mask)
+		};
+	}
+
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetStencilReference`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdSetStencilReference.html).
+	pub fn set_dynamic_stencil_reference(&self, faces: flags::StencilFace, reference: u32) {
+		self.debug_check_dynamic(flags::DynamicState::STENCIL_REFERENCE);
+		unsafe {
+			self.device
+				.cmd_set_stencil_reference(self.internal, faces, reference)
+		};
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetCullMode`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetCullMode.html).
+	pub fn set_dynamic_cull_mode(&self, cull_mode: flags::CullMode) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::CULL_MODE)?;
+		unsafe { extension.cmd_set_cull_mode(self.internal, cull_mode) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetFrontFace`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetFrontFace.html).
+	pub fn set_dynamic_front_face(&self, front_face: flags::FrontFace) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::FRONT_FACE)?;
+		unsafe { extension.cmd_set_front_face(self.internal, front_face) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetPrimitiveTopology`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetPrimitiveTopology.html).
+	pub fn set_dynamic_primitive_topology(
+		&self,
+		topology: flags::PrimitiveTopology,
+	) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::PRIMITIVE_TOPOLOGY)?;
+		unsafe { extension.cmd_set_primitive_topology(self.internal, topology) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthTestEnable`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetDepthTestEnable.html).
+	pub fn set_dynamic_depth_test_enable(&self, enabled: bool) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::DEPTH_TEST_ENABLE)?;
+		unsafe { extension.cmd_set_depth_test_enable(self.internal, enabled) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthWriteEnable`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetDepthWriteEnable.html).
+	pub fn set_dynamic_depth_write_enable(&self, enabled: bool) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::DEPTH_WRITE_ENABLE)?;
+		unsafe { extension.cmd_set_depth_write_enable(self.internal, enabled) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthCompareOp`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetDepthCompareOp.html).
+	pub fn set_dynamic_depth_compare_op(&self, op: flags::CompareOp) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::DEPTH_COMPARE_OP)?;
+		unsafe { extension.cmd_set_depth_compare_op(self.internal, op) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetDepthBoundsTestEnable`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetDepthBoundsTestEnable.html).
+	pub fn set_dynamic_depth_bounds_test_enable(&self, enabled: bool) -> utility::Result<()> {
+		let extension =
+			self.extended_dynamic_state(flags::DynamicState::DEPTH_BOUNDS_TEST_ENABLE)?;
+		unsafe { extension.cmd_set_depth_bounds_test_enable(self.internal, enabled) };
+		Ok(())
+	}
+
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetStencilTestEnable`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetStencilTestEnable.html).
+	pub fn set_dynamic_stencil_test_enable(&self, enabled: bool) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::STENCIL_TEST_ENABLE)?;
+		unsafe { extension.cmd_set_stencil_test_enable(self.internal, enabled) };
+		Ok(())
+	}
+
+	/// Sets the stencil operations of the `faces`.
+	/// The operations are applied when the stencil test fails (`fail`), when both the stencil and depth tests pass (`pass`),
+	/// and when the stencil test passes but the depth test fails (`depth_fail`).
+	///
+	/// Requires [`extended dynamic state`](logical::Info::with_extended_dynamic_state).
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdSetStencilOp`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdSetStencilOp.html).
+	pub fn set_dynamic_stencil_op(
+		&self,
+		faces: flags::StencilFace,
+		fail: flags::StencilOp,
+		pass: flags::StencilOp,
+		depth_fail: flags::StencilOp,
+		compare: flags::CompareOp,
+	) -> utility::Result<()> {
+		let extension = self.extended_dynamic_state(flags::DynamicState::STENCIL_OP)?;
+		unsafe {
+			extension.cmd_set_stencil_op(self.internal, faces, fail, pass, depth_fail, compare)
+		};
+		Ok(())
+	}
+
+	fn extended_dynamic_state(
+		&self,
+		state: flags::DynamicState,
+	) -> utility::Result<&backend::extensions::ext::ExtendedDynamicState> {
+		let extension = self.device.extended_dynamic_state().ok_or_else(|| {
+			utility::Error::FeatureNotEnabled("VK_EXT_extended_dynamic_state".to_owned())
+		})?;
+		self.debug_check_dynamic(state);
+		Ok(extension)
+	}
+
+	/// Panics in debug builds if the bound graphics pipeline did not declare `state` as dynamic.
+	fn debug_check_dynamic(&self, state: flags::DynamicState) {
+		if let Some(states) = self.bound_dynamic_states.as_ref() {
+			debug_assert!(
+				states.contains(&state),
+				"Cannot set {:?} on {:?}, the bound pipeline did not declare it as dynamic",
+				state,
+				self.name
+			);
+		}
+	}
+}
+
+/// Query operations
+impl Buffer {
+	/// Resets a range of queries in a pool, so that they can be written to by later commands.
+	/// Queries must be reset before they are first used, and before each time they are reused.
+	/// Returns an error if the range is inverted or extends past the end of the pool.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and outside of a render pass.
+	///
+	/// Equivalent to [`vkCmdResetQueryPool`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdResetQueryPool.html).
+	pub fn reset_queries(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		queries: Range<u32>,
+	) -> utility::Result<()> {
+		pool.validate_range(&queries)?;
+		let count = queries.end - queries.start;
+		unsafe {
+			self.device
+				.cmd_reset_query_pool(self.internal, ***pool, queries.start, count)
+		};
+		self.bound_objects.push(pool.clone());
+		Ok(())
+	}
+
+	/// Starts an [`occlusion`](query::Kind::Occlusion) or [`pipeline statistics`](query::Kind::PipelineStatistics) query.
+	/// If `precise` is true, an occlusion query counts the exact number of samples which pass
+	/// (requires the `occlusionQueryPrecise` device feature), instead of only whether any samples passed.
+	/// Returns an error if the index is not in the pool, or if `precise` is true for a pool which is not an occlusion pool.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdBeginQuery`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdBeginQuery.html).
+	pub fn begin_query(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		index: u32,
+		precise: bool,
+	) -> utility::Result<()> {
+		use utility::NamedObject;
+		pool.validate_index(index)?;
+		if pool.kind() == query::Kind::Timestamp {
+			return Err(utility::Error::InvalidQuery(format!(
+				"{:?} contains timestamp queries, which are written with write_timestamp instead of begin_query",
+				pool.name()
+			)));
+		}
+		if precise && pool.kind() != query::Kind::Occlusion {
+			return Err(utility::Error::InvalidQuery(format!(
+				"{:?} contains {:?} queries, only occlusion queries can be precise",
+				pool.name(),
+				pool.kind()
+			)));
+		}
+		let flags = match precise {
+			true => backend::vk::QueryControlFlags::PRECISE,
+			false => backend::vk::QueryControlFlags::empty(),
+		};
+		unsafe {
+			self.device
+				.cmd_begin_query(self.internal, ***pool, index, flags)
+		};
+		self.active_queries.push(ActiveQuery {
+			pool: ***pool,
+			index,
+			kind: pool.kind(),
+			precise,
+		});
+		self.bound_objects.push(pool.clone());
+		Ok(())
+	}
+
+	/// Stops a query which was started by [`begin_query`](Buffer::begin_query).
+	///
+	/// Returns an error if the index is not in the pool.
+	///
+	/// Can only be called after [`begin_query`](Buffer::begin_query) in the same subpass.
+	///
+	/// Equivalent to [`vkCmdEndQuery`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdEndQuery.html).
+	pub fn end_query(&mut self, pool: &Arc<query::Pool>, index: u32) -> utility::Result<()> {
+		pool.validate_index(index)?;
+		unsafe { self.device.cmd_end_query(self.internal, ***pool, index) };
+		self.active_queries
+			.retain(|active| active.pool != ***pool || active.index != index);
+		Ok(())
+	}
+
+	/// Writes the GPU's timestamp into a [`timestamp`](query::Kind::Timestamp) query
+	/// once all previous commands have completed `stage`.
+	///
+	/// Can only be called after [`begin`](Buffer::begin) and before [`end`](Buffer::end).
+	///
+	/// Equivalent to [`vkCmdWriteTimestamp2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkCmdWriteTimestamp2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkCmdWriteTimestamp`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkCmdWriteTimestamp.html).
+	pub fn write_timestamp(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		index: u32,
+		stage: flags::PipelineStage,
+	) -> utility::Result<()> {
+		pool.validate_index(index)?;
+		if pool.kind() != query::Kind::Timestamp {
+			use utility::NamedObject;
+			return Err(utility::Error::InvalidQuery(format!(
+				"{:?} contains {:?} queries, timestamps can only be written to timestamp queries",
+				pool.name(),
+				pool.kind()
+			)));
+		}
+		match self.device.synchronization2() {
+			Some(synchronization2) => unsafe {
+				synchronization2.cmd_write_timestamp2(
+					self.internal,
+					flags::StageSet::from(stage).as_vk2(),
+					***pool,
+					index,
+				)
+			},
+			None => unsafe {
+				self.device
+					.cmd_write_timestamp(self.internal, stage.into(), ***pool, index)
+			},
+		}
+		self.bound_objects.push(pool.clone());
+		Ok(())
+	}
+
+	fn is_occlusion_query_active(&self) -> bool {
+		self.active_queries
+			.iter()
+			.any(|active| active.kind == query::Kind::Occlusion)
+	}
+
+	fn inherited_query_flags(&self) -> backend::vk::QueryControlFlags {
+		match self
+			.active_queries
+			.iter()
+			.any(|active| active.kind == query::Kind::Occlusion && active.precise)
+		{
+			true => backend::vk::QueryControlFlags::PRECISE,
+			false => backend::vk::QueryControlFlags::empty(),
+		}
+	}
+
+	fn inherited_pipeline_statistics(&self) -> flags::QueryPipelineStatistic {
+		self.active_queries
+			.iter()
+			.fold(
+				flags::QueryPipelineStatistic::empty(),
+				|flags, active| match active.kind {
+					query::Kind::PipelineStatistics(statistics) => flags | statistics,
+					_ => flags,
+				},
+			)
+	}
 }
 
 impl std::ops::Deref for Buffer {
@@ -0,0 +1,172 @@
+use crate::{
+	command::{Fence, TimelineSemaphore, WaitStatus},
+	utility,
+};
+use std::{
+	future::Future,
+	pin::Pin,
+	sync::{self, mpsc},
+	task::{Context, Poll, Waker},
+	thread,
+};
+
+/// How long the waiter thread blocks on a single object before checking for new requests, in nanoseconds.
+const WAIT_SLICE: u64 = 1_000_000;
+
+/// Owns a background thread which waits on [`Fences`](Fence) and [`TimelineSemaphores`](TimelineSemaphore),
+/// completing a [`Completion`] future for each when it signals.
+/// This allows async code to `.await` work on the GPU without blocking an executor thread.
+///
+/// Dropping the waiter does not cancel outstanding completions;
+/// the thread exits once all of the objects it is waiting on have signaled.
+pub struct Waiter {
+	// Dropping the sender disconnects the channel, which lets the thread exit once its pending requests have completed.
+	sender: mpsc::Sender<Request>,
+}
+
+enum Target {
+	Fence(sync::Arc<Fence>),
+	Timeline(sync::Arc<TimelineSemaphore>, u64),
+}
+
+impl Target {
+	fn wait(&self, timeout: u64) -> utility::Result<WaitStatus> {
+		match self {
+			Self::Fence(fence) => fence.wait(timeout),
+			Self::Timeline(semaphore, value) => semaphore.wait(*value, timeout),
+		}
+	}
+}
+
+struct Request {
+	target: Target,
+	state: sync::Arc<sync::Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+	result: Option<utility::Result<()>>,
+	complete: bool,
+	waker: Option<Waker>,
+}
+
+impl Request {
+	fn complete(self, result: utility::Result<()>) {
+		let mut state = self.state.lock().unwrap();
+		state.result = Some(result);
+		state.complete = true;
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+	}
+}
+
+impl Waiter {
+	pub fn new(name: &str) -> utility::Result<Self> {
+		let (sender, receiver) = mpsc::channel();
+		thread::Builder::new()
+			.name(name.to_owned())
+			.spawn(move || Self::run(receiver))
+			.map_err(utility::Error::General)?;
+		Ok(Self { sender })
+	}
+
+	fn run(receiver: mpsc::Receiver<Request>) {
+		let mut pending: Vec<Request> = Vec::new();
+		let mut connected = true;
+		loop {
+			// Block for new requests when there is nothing else to do.
+			if pending.is_empty() {
+				match receiver.recv() {
+					Ok(request) => pending.push(request),
+					Err(_) => return,
+				}
+			}
+			while connected {
+				match receiver.try_recv() {
+					Ok(request) => pending.push(request),
+					Err(mpsc::TryRecvError::Empty) => break,
+					Err(mpsc::TryRecvError::Disconnected) => connected = false,
+				}
+			}
+
+			let mut completed_any = false;
+			let mut still_pending = Vec::with_capacity(pending.len());
+			for request in pending.drain(..) {
+				match request.target.wait(0) {
+					Ok(WaitStatus::TimedOut) => still_pending.push(request),
+					Ok(WaitStatus::Ready) => {
+						completed_any = true;
+						request.complete(Ok(()));
+					}
+					Err(err) => {
+						completed_any = true;
+						request.complete(Err(err));
+					}
+				}
+			}
+			pending = still_pending;
+
+			if pending.is_empty() {
+				if !connected {
+					return;
+				}
+			} else if !completed_any {
+				// Sleep on the oldest request, so that the thread does not spin while the GPU is busy.
+				let _ = pending[0].target.wait(WAIT_SLICE);
+			}
+		}
+	}
+
+	fn submit(&self, target: Target) -> Completion {
+		let state = sync::Arc::new(sync::Mutex::new(State::default()));
+		let request = Request {
+			target,
+			state: state.clone(),
+		};
+		if let Err(mpsc::SendError(request)) = self.sender.send(request) {
+			let err = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "waiter thread exited");
+			request.complete(Err(utility::Error::General(err)));
+		}
+		Completion { state }
+	}
+
+	/// Returns a future which completes when the fence is signaled.
+	pub fn fence(&self, fence: &sync::Arc<Fence>) -> Completion {
+		self.submit(Target::Fence(fence.clone()))
+	}
+
+	/// Returns a future which completes when the timeline's counter reaches at least `value`.
+	pub fn timeline(&self, semaphore: &sync::Arc<TimelineSemaphore>, value: u64) -> Completion {
+		self.submit(Target::Timeline(semaphore.clone(), value))
+	}
+}
+
+/// A future which resolves when a [`Fence`] or [`TimelineSemaphore`] signals,
+/// created by a [`Waiter`].
+pub struct Completion {
+	state: sync::Arc<sync::Mutex<State>>,
+}
+
+impl Completion {
+	/// Returns true if the object has signaled (or waiting on it failed).
+	pub fn is_complete(&self) -> bool {
+		self.state.lock().unwrap().complete
+	}
+}
+
+impl Future for Completion {
+	type Output = utility::Result<()>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut state = self.state.lock().unwrap();
+		match state.result.take() {
+			Some(result) => Poll::Ready(result),
+			None if state.complete => panic!("Completion polled after it resolved"),
+			None => {
+				state.waker = Some(cx.waker().clone());
+				Poll::Pending
+			}
+		}
+	}
+}
@@ -0,0 +1,86 @@
+use crate::{
+	backend,
+	device::logical,
+	utility::{self, HandledObject},
+};
+
+use std::sync;
+
+/// A signal which can be set and reset by both the CPU and the GPU,
+/// and waited on by commands later in the same queue.
+///
+/// Used to split a [`pipeline barrier`](crate::command::barrier::Pipeline) in two,
+/// so that independent work can be recorded between the [`set`](crate::command::Buffer::set_event)
+/// and the [`wait`](crate::command::Buffer::wait_events) instead of stalling at a single point.
+pub struct Event {
+	internal: backend::vk::Event,
+	device: sync::Arc<logical::Device>,
+	name: String,
+}
+
+impl Event {
+	pub fn new(device: &sync::Arc<logical::Device>, name: &str) -> utility::Result<Event> {
+		let info = backend::vk::EventCreateInfo::builder().build();
+		let internal = unsafe { device.create_event(&info, None) }?;
+		let event = Event {
+			device: device.clone(),
+			internal,
+			name: name.to_owned(),
+		};
+		device.set_object_name_logged(&event.create_name(name));
+		Ok(event)
+	}
+
+	/// Returns true if the event is set, without blocking.
+	///
+	/// Equivalent to [`vkGetEventStatus`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkGetEventStatus.html).
+	pub fn status(&self) -> utility::Result<bool> {
+		Ok(unsafe { self.device.get_event_status(self.internal) }?)
+	}
+
+	/// Sets the event from the CPU, releasing any commands which are waiting on it.
+	///
+	/// Equivalent to [`vkSetEvent`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkSetEvent.html).
+	pub fn set(&self) -> utility::Result<()> {
+		Ok(unsafe { self.device.set_event(self.internal) }?)
+	}
+
+	/// Returns the event to the unset state from the CPU.
+	/// The event must not be waited on by a pending submission.
+	///
+	/// Equivalent to [`vkResetEvent`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkResetEvent.html).
+	pub fn reset(&self) -> utility::Result<()> {
+		Ok(unsafe { self.device.reset_event(self.internal) }?)
+	}
+}
+
+impl std::ops::Deref for Event {
+	type Target = backend::vk::Event;
+	fn deref(&self) -> &Self::Target {
+		&self.internal
+	}
+}
+
+impl Drop for Event {
+	fn drop(&mut self) {
+		log::debug!(target: crate::LOG, "Dropping Event: {:?}", self.name);
+		unsafe { self.device.destroy_event(self.internal, None) };
+	}
+}
+
+impl utility::NamedObject for Event {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
+impl HandledObject for Event {
+	fn kind(&self) -> backend::vk::ObjectType {
+		<backend::vk::Event as backend::vk::Handle>::TYPE
+	}
+
+	fn handle(&self) -> u64 {
+		use backend::vk::Handle;
+		self.internal.as_raw()
+	}
+}
@@ -0,0 +1,1520 @@
+//! An intermediate representation of recorded commands, which can be inspected, dumped,
+//! and encoded into a [`command buffer`](crate::command::Buffer) later (and on another thread).
+//!
+//! Every recording method of [`command::Buffer`] has a matching [`Command`].
+
+use crate::{
+	backend, buffer,
+	command::{self, barrier, frame},
+	descriptor, flags, image, pipeline, query, renderpass,
+	structs::subresource,
+	utility::{self, HandledObject, NamedObject, Scissor, Viewport},
+};
+use enumset::EnumSet;
+use std::{ops::Range, sync::Arc};
+
+/// A single command in a [`List`], mirroring a method of [`command::Buffer`].
+/// Each variant holds the resources it uses, so they are kept alive until the list is encoded.
+#[derive(Clone)]
+pub enum Command {
+	/// See [`command::Buffer::begin_label`].
+	BeginLabel { name: String, color: [f32; 4] },
+	/// See [`command::Buffer::insert_label`].
+	InsertLabel { name: String, color: [f32; 4] },
+	/// See [`command::Buffer::end_label`].
+	EndLabel,
+	/// See [`command::Buffer::mark_pipeline_barrier`].
+	PipelineBarrier(barrier::Pipeline),
+	/// See [`command::Buffer::set_event`].
+	SetEvent {
+		event: Arc<command::Event>,
+		barrier: barrier::Pipeline,
+	},
+	/// See [`command::Buffer::wait_events`].
+	WaitEvents(Vec<(Arc<command::Event>, barrier::Pipeline)>),
+	/// See [`command::Buffer::reset_event`].
+	ResetEvent {
+		event: Arc<command::Event>,
+		stages: flags::StageSet,
+	},
+	/// See [`command::Buffer::transition_all`].
+	Transition(Vec<barrier::Transition>),
+	/// See [`command::Buffer::release_ownership`].
+	ReleaseOwnership(Arc<command::OwnershipTransfer>),
+	/// See [`command::Buffer::acquire_ownership`].
+	AcquireOwnership(Arc<command::OwnershipTransfer>),
+	/// See [`command::Buffer::copy_buffer_to_buffer`].
+	CopyBufferToBuffer {
+		src: Arc<buffer::Buffer>,
+		dst: Arc<buffer::Buffer>,
+		regions: Vec<command::CopyBufferRange>,
+	},
+	/// See [`command::Buffer::fill_buffer`]. A `size` of None fills the rest of the buffer.
+	FillBuffer {
+		buffer: Arc<buffer::Buffer>,
+		offset: usize,
+		size: Option<usize>,
+		data: u32,
+	},
+	/// See [`command::Buffer::update_buffer`]. Created by [`Command::update_buffer`].
+	UpdateBuffer {
+		buffer: Arc<buffer::Buffer>,
+		offset: usize,
+		data: Vec<u8>,
+	},
+	/// See [`command::Buffer::copy_buffer_to_image`].
+	CopyBufferToImage {
+		buffer: Arc<buffer::Buffer>,
+		image: Arc<image::Image>,
+		layout: flags::ImageLayout,
+		regions: Vec<command::CopyBufferToImage>,
+	},
+	/// See [`command::Buffer::copy_image_to_buffer`].
+	CopyImageToBuffer {
+		image: Arc<image::Image>,
+		layout: flags::ImageLayout,
+		buffer: Arc<buffer::Buffer>,
+		regions: Vec<command::CopyImageToBuffer>,
+	},
+	/// See [`command::Buffer::copy_image_to_image`].
+	CopyImageToImage {
+		src: Arc<image::Image>,
+		src_layout: flags::ImageLayout,
+		dst: Arc<image::Image>,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	},
+	/// See [`command::Buffer::blit_image`].
+	BlitImage {
+		src: Arc<image::Image>,
+		src_layout: flags::ImageLayout,
+		dst: Arc<image::Image>,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::BlitImage>,
+		filter: flags::Filter,
+	},
+	/// See [`command::Buffer::resolve_image`].
+	ResolveImage {
+		src: Arc<image::Image>,
+		src_layout: flags::ImageLayout,
+		dst: Arc<image::Image>,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	},
+	/// See [`command::Buffer::clear_image`].
+	ClearImage {
+		image: Arc<image::Image>,
+		layout: flags::ImageLayout,
+		value: renderpass::ClearValue,
+		ranges: Vec<subresource::Range>,
+	},
+	/// See [`command::Buffer::start_render_pass`].
+	StartRenderPass {
+		frame_buffer: Arc<frame::Buffer>,
+		render_pass: Arc<renderpass::Pass>,
+		instruction: renderpass::RecordInstruction,
+		uses_secondary_buffers: bool,
+	},
+	/// See [`command::Buffer::next_subpass`].
+	NextSubpass { uses_secondary_buffers: bool },
+	/// See [`command::Buffer::stop_render_pass`].
+	StopRenderPass,
+	/// See [`command::Buffer::execute`].
+	Execute(Vec<Arc<command::Buffer>>),
+	/// See [`command::Buffer::clear_attachments`].
+	ClearAttachments {
+		attachments: Vec<command::ClearAttachment>,
+		rects: Vec<command::ClearRect>,
+	},
+	/// See [`command::Buffer::bind_pipeline`].
+	BindPipeline {
+		pipeline: Arc<pipeline::Pipeline>,
+		bind_point: flags::PipelineBindPoint,
+	},
+	/// See [`command::Buffer::bind_descriptors`].
+	BindDescriptors {
+		bind_point: flags::PipelineBindPoint,
+		layout: Arc<pipeline::layout::Layout>,
+		first_set_index: usize,
+		sets: Vec<Arc<descriptor::Set>>,
+	},
+	/// See [`command::Buffer::push_constant`]. Created by [`Command::push_constant`].
+	PushConstant {
+		layout: Arc<pipeline::layout::Layout>,
+		stages: EnumSet<flags::ShaderKind>,
+		offset: usize,
+		data: Vec<u8>,
+	},
+	/// See [`command::Buffer::set_dynamic_viewport`].
+	SetViewport {
+		first: usize,
+		viewports: Vec<Viewport>,
+	},
+	/// See [`command::Buffer::set_dynamic_scissors`].
+	SetScissors {
+		first: usize,
+		scissors: Vec<Scissor>,
+	},
+	/// See [`command::Buffer::set_dynamic_line_width`].
+	SetLineWidth(f32),
+	/// See [`command::Buffer::set_dynamic_depth_bias`].
+	SetDepthBias {
+		constant_factor: f32,
+		clamp: f32,
+		slope_factor: f32,
+	},
+	/// See [`command::Buffer::set_dynamic_blend_constants`].
+	SetBlendConstants([f32; 4]),
+	/// See [`command::Buffer::set_dynamic_stencil_reference`].
+	SetStencilReference {
+		faces: flags::StencilFace,
+		reference: u32,
+	},
+	/// See [`command::Buffer::set_dynamic_depth_bounds`].
+	SetDepthBounds { min: f32, max: f32 },
+	/// See [`command::Buffer::set_dynamic_stencil_compare_mask`].
+	SetStencilCompareMask {
+		faces: flags::StencilFace,
+		mask: u32,
+	},
+	/// See [`command::Buffer::set_dynamic_stencil_write_mask`].
+	SetStencilWriteMask {
+		faces: flags::StencilFace,
+		mask: u32,
+	},
+	/// See [`command::Buffer::set_dynamic_cull_mode`].
+	SetCullMode(flags::CullMode),
+	/// See [`command::Buffer::set_dynamic_front_face`].
+	SetFrontFace(flags::FrontFace),
+	/// See [`command::Buffer::set_dynamic_primitive_topology`].
+	SetPrimitiveTopology(flags::PrimitiveTopology),
+	/// See [`command::Buffer::set_dynamic_depth_test_enable`].
+	SetDepthTestEnable(bool),
+	/// See [`command::Buffer::set_dynamic_depth_write_enable`].
+	SetDepthWriteEnable(bool),
+	/// See [`command::Buffer::set_dynamic_depth_compare_op`].
+	SetDepthCompareOp(flags::CompareOp),
+	/// See [`command::Buffer::set_dynamic_depth_bounds_test_enable`].
+	SetDepthBoundsTestEnable(bool),
+	/// See [`command::Buffer::set_dynamic_stencil_test_enable`].
+	SetStencilTestEnable(bool),
+	/// See [`command::Buffer::set_dynamic_stencil_op`].
+	SetStencilOp {
+		faces: flags::StencilFace,
+		fail: flags::StencilOp,
+		pass: flags::StencilOp,
+		depth_fail: flags::StencilOp,
+		compare: flags::CompareOp,
+	},
+	/// See [`command::Buffer::bind_vertex_buffers`].
+	BindVertexBuffers {
+		first_binding: u32,
+		buffers: Vec<Arc<buffer::Buffer>>,
+		offsets: Vec<u64>,
+	},
+	/// See [`command::Buffer::bind_index_buffer`].
+	BindIndexBuffer {
+		buffer: Arc<buffer::Buffer>,
+		offset: u64,
+	},
+	/// See [`command::Buffer::draw`].
+	Draw {
+		index_count: usize,
+		first_index: usize,
+		instance_count: usize,
+		first_instance: usize,
+		vertex_offset: usize,
+	},
+	/// See [`command::Buffer::draw_vertices`].
+	DrawVertices {
+		vertex_count: usize,
+		first_vertex: usize,
+		instance_count: usize,
+		first_instance: usize,
+	},
+	/// See [`command::Buffer::draw_indirect`].
+	DrawIndirect {
+		draws: IndirectDraws,
+		draw_count: usize,
+	},
+	/// See [`command::Buffer::draw_indexed_indirect`].
+	DrawIndexedIndirect {
+		draws: IndirectDraws,
+		draw_count: usize,
+	},
+	/// See [`command::Buffer::draw_indirect_count`].
+	DrawIndirectCount {
+		draws: IndirectDraws,
+		count_buffer: Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	},
+	/// See [`command::Buffer::draw_indexed_indirect_count`].
+	DrawIndexedIndirectCount {
+		draws: IndirectDraws,
+		count_buffer: Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	},
+	/// See [`command::Buffer::dispatch`].
+	Dispatch { group_counts: [u32; 3] },
+	/// See [`command::Buffer::dispatch_base`].
+	DispatchBase {
+		base_group: [u32; 3],
+		group_counts: [u32; 3],
+	},
+	/// See [`command::Buffer::dispatch_indirect`].
+	DispatchIndirect {
+		buffer: Arc<buffer::Buffer>,
+		offset: usize,
+	},
+	/// See [`command::Buffer::reset_queries`].
+	ResetQueries {
+		pool: Arc<query::Pool>,
+		queries: Range<u32>,
+	},
+	/// See [`command::Buffer::begin_query`].
+	BeginQuery {
+		pool: Arc<query::Pool>,
+		index: u32,
+		precise: bool,
+	},
+	/// See [`command::Buffer::end_query`].
+	EndQuery { pool: Arc<query::Pool>, index: u32 },
+	/// See [`command::Buffer::write_timestamp`].
+	WriteTimestamp {
+		pool: Arc<query::Pool>,
+		index: u32,
+		stage: flags::PipelineStage,
+	},
+}
+
+impl Command {
+	/// Creates an [`UpdateBuffer`](Command::UpdateBuffer) command which writes `data` at `offset`.
+	pub fn update_buffer<T>(buffer: &Arc<buffer::Buffer>, offset: usize, data: &T) -> Self
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		Self::UpdateBuffer {
+			buffer: buffer.clone(),
+			offset,
+			data: bytemuck::bytes_of(data).to_vec(),
+		}
+	}
+
+	/// Creates a [`PushConstant`](Command::PushConstant) command which pushes `data` at `offset`.
+	pub fn push_constant<T>(
+		layout: &Arc<pipeline::layout::Layout>,
+		stages: impl Into<EnumSet<flags::ShaderKind>>,
+		offset: usize,
+		data: &T,
+	) -> Self
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		Self::PushConstant {
+			layout: layout.clone(),
+			stages: stages.into(),
+			offset,
+			data: bytemuck::bytes_of(data).to_vec(),
+		}
+	}
+
+	/// The name of the command, as used in text and JSON dumps (i.e. `"draw"`).
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::BeginLabel { .. } => "begin_label",
+			Self::InsertLabel { .. } => "insert_label",
+			Self::EndLabel => "end_label",
+			Self::PipelineBarrier(_) => "pipeline_barrier",
+			Self::SetEvent { .. } => "set_event",
+			Self::WaitEvents(_) => "wait_events",
+			Self::ResetEvent { .. } => "reset_event",
+			Self::Transition(_) => "transition",
+			Self::ReleaseOwnership(_) => "release_ownership",
+			Self::AcquireOwnership(_) => "acquire_ownership",
+			Self::CopyBufferToBuffer { .. } => "copy_buffer_to_buffer",
+			Self::FillBuffer { .. } => "fill_buffer",
+			Self::UpdateBuffer { .. } => "update_buffer",
+			Self::CopyBufferToImage { .. } => "copy_buffer_to_image",
+			Self::CopyImageToBuffer { .. } => "copy_image_to_buffer",
+			Self::CopyImageToImage { .. } => "copy_image_to_image",
+			Self::BlitImage { .. } => "blit_image",
+			Self::ResolveImage { .. } => "resolve_image",
+			Self::ClearImage { .. } => "clear_image",
+			Self::StartRenderPass { .. } => "start_render_pass",
+			Self::NextSubpass { .. } => "next_subpass",
+			Self::StopRenderPass => "stop_render_pass",
+			Self::Execute(_) => "execute",
+			Self::ClearAttachments { .. } => "clear_attachments",
+			Self::BindPipeline { .. } => "bind_pipeline",
+			Self::BindDescriptors { .. } => "bind_descriptors",
+			Self::PushConstant { .. } => "push_constant",
+			Self::SetViewport { .. } => "set_viewport",
+			Self::SetScissors { .. } => "set_scissors",
+			Self::SetLineWidth(_) => "set_line_width",
+			Self::SetDepthBias { .. } => "set_depth_bias",
+			Self::SetBlendConstants(_) => "set_blend_constants",
+			Self::SetStencilReference { .. } => "set_stencil_reference",
+			Self::SetDepthBounds { .. } => "set_depth_bounds",
+			Self::SetStencilCompareMask { .. } => "set_stencil_compare_mask",
+			Self::SetStencilWriteMask { .. } => "set_stencil_write_mask",
+			Self::SetCullMode(_) => "set_cull_mode",
+			Self::SetFrontFace(_) => "set_front_face",
+			Self::SetPrimitiveTopology(_) => "set_primitive_topology",
+			Self::SetDepthTestEnable(_) => "set_depth_test_enable",
+			Self::SetDepthWriteEnable(_) => "set_depth_write_enable",
+			Self::SetDepthCompareOp(_) => "set_depth_compare_op",
+			Self::SetDepthBoundsTestEnable(_) => "set_depth_bounds_test_enable",
+			Self::SetStencilTestEnable(_) => "set_stencil_test_enable",
+			Self::SetStencilOp { .. } => "set_stencil_op",
+			Self::BindVertexBuffers { .. } => "bind_vertex_buffers",
+			Self::BindIndexBuffer { .. } => "bind_index_buffer",
+			Self::Draw { .. } => "draw",
+			Self::DrawVertices { .. } => "draw_vertices",
+			Self::DrawIndirect { .. } => "draw_indirect",
+			Self::DrawIndexedIndirect { .. } => "draw_indexed_indirect",
+			Self::DrawIndirectCount { .. } => "draw_indirect_count",
+			Self::DrawIndexedIndirectCount { .. } => "draw_indexed_indirect_count",
+			Self::Dispatch { .. } => "dispatch",
+			Self::DispatchBase { .. } => "dispatch_base",
+			Self::DispatchIndirect { .. } => "dispatch_indirect",
+			Self::ResetQueries { .. } => "reset_queries",
+			Self::BeginQuery { .. } => "begin_query",
+			Self::EndQuery { .. } => "end_query",
+			Self::WriteTimestamp { .. } => "write_timestamp",
+		}
+	}
+
+	/// The arguments of the command, in the order they are dumped.
+	fn args(&self) -> Vec<(&'static str, Arg)> {
+		use Arg::*;
+		match self {
+			Self::BeginLabel { name, color } | Self::InsertLabel { name, color } => {
+				vec![("name", Str(name.clone())), ("color", floats(color))]
+			}
+			Self::EndLabel | Self::StopRenderPass => vec![],
+			Self::PipelineBarrier(barrier) => barrier_args(barrier),
+			Self::SetEvent { event, barrier } => {
+				let mut args = vec![("event", named(&**event))];
+				args.append(&mut barrier_args(barrier));
+				args
+			}
+			Self::WaitEvents(events) => vec![(
+				"events",
+				List(
+					events
+						.iter()
+						.map(|(event, barrier)| {
+							let mut args = vec![named(&**event)];
+							args.extend(barrier_args(barrier).into_iter().map(|(_, arg)| arg));
+							List(args)
+						})
+						.collect(),
+				),
+			)],
+			Self::ResetEvent { event, stages } => vec![
+				("event", named(&**event)),
+				("stages", Ident(format!("{:?}", stages.as_vk2()))),
+			],
+			Self::Transition(transitions) => vec![(
+				"transitions",
+				List(transitions.iter().map(transition_arg).collect()),
+			)],
+			Self::ReleaseOwnership(transfer) => {
+				let mut args = vec![("transfer", Str(transfer.name().to_owned()))];
+				args.append(&mut barrier_args(&transfer.barrier(command::Half::Release)));
+				args
+			}
+			Self::AcquireOwnership(transfer) => {
+				let mut args = vec![("transfer", Str(transfer.name().to_owned()))];
+				args.append(&mut barrier_args(&transfer.barrier(command::Half::Acquire)));
+				args
+			}
+			Self::CopyBufferToBuffer { src, dst, regions } => vec![
+				("src", named(&**src)),
+				("dst", named(&**dst)),
+				(
+					"regions",
+					List(
+						regions
+							.iter()
+							.map(|region| Ident(format!("{:?}", region)))
+							.collect(),
+					),
+				),
+			],
+			Self::FillBuffer {
+				buffer,
+				offset,
+				size,
+				data,
+			} => vec![
+				("buffer", named(&**buffer)),
+				("offset", UInt(*offset as u64)),
+				(
+					"size",
+					size.map_or(Ident("whole".to_owned()), |size| UInt(size as u64)),
+				),
+				("data", UInt(*data as u64)),
+			],
+			Self::UpdateBuffer {
+				buffer,
+				offset,
+				data,
+			} => vec![
+				("buffer", named(&**buffer)),
+				("offset", UInt(*offset as u64)),
+				("size", UInt(data.len() as u64)),
+			],
+			Self::CopyBufferToImage {
+				buffer,
+				image,
+				layout,
+				regions,
+			} => vec![
+				("buffer", named(&**buffer)),
+				("image", named(&**image)),
+				("layout", Ident(format!("{:?}", layout))),
+				("regions", debug_list(regions)),
+			],
+			Self::CopyImageToBuffer {
+				image,
+				layout,
+				buffer,
+				regions,
+			} => vec![
+				("image", named(&**image)),
+				("layout", Ident(format!("{:?}", layout))),
+				("buffer", named(&**buffer)),
+				("regions", debug_list(regions)),
+			],
+			Self::CopyImageToImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+			}
+			| Self::ResolveImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+			} => vec![
+				("src", named(&**src)),
+				("src_layout", Ident(format!("{:?}", src_layout))),
+				("dst", named(&**dst)),
+				("dst_layout", Ident(format!("{:?}", dst_layout))),
+				("regions", debug_list(regions)),
+			],
+			Self::BlitImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+				filter,
+			} => vec![
+				("src", named(&**src)),
+				("src_layout", Ident(format!("{:?}", src_layout))),
+				("dst", named(&**dst)),
+				("dst_layout", Ident(format!("{:?}", dst_layout))),
+				("regions", debug_list(regions)),
+				("filter", Ident(format!("{:?}", filter))),
+			],
+			Self::ClearImage {
+				image,
+				layout,
+				value,
+				ranges,
+			} => vec![
+				("image", named(&**image)),
+				("layout", Ident(format!("{:?}", layout))),
+				("value", Ident(format!("{:?}", value))),
+				("ranges", debug_list(ranges)),
+			],
+			Self::StartRenderPass {
+				frame_buffer,
+				render_pass,
+				instruction,
+				uses_secondary_buffers,
+			} => vec![
+				("render_pass", named(&**render_pass)),
+				("frame_buffer", handle(&**frame_buffer)),
+				(
+					"render_area",
+					Ident(format!("{:?}", instruction.render_area)),
+				),
+				("uses_secondary_buffers", Bool(*uses_secondary_buffers)),
+			],
+			Self::NextSubpass {
+				uses_secondary_buffers,
+			} => vec![("uses_secondary_buffers", Bool(*uses_secondary_buffers))],
+			Self::Execute(buffers) => vec![(
+				"buffers",
+				List(buffers.iter().map(|buffer| named(&**buffer)).collect()),
+			)],
+			Self::ClearAttachments { attachments, rects } => vec![
+				("attachments", debug_list(attachments)),
+				("rects", debug_list(rects)),
+			],
+			Self::BindPipeline {
+				pipeline,
+				bind_point,
+			} => vec![
+				("pipeline", named(&**pipeline)),
+				("bind_point", Ident(format!("{:?}", bind_point))),
+			],
+			Self::BindDescriptors {
+				bind_point,
+				layout,
+				first_set_index,
+				sets,
+			} => vec![
+				("bind_point", Ident(format!("{:?}", bind_point))),
+				("layout", named(&**layout)),
+				("first_set_index", UInt(*first_set_index as u64)),
+				(
+					"sets",
+					List(sets.iter().map(|set| handle(&**set)).collect()),
+				),
+			],
+			Self::PushConstant {
+				layout,
+				stages,
+				offset,
+				data,
+			} => vec![
+				("layout", named(&**layout)),
+				("stages", shader_stages(*stages)),
+				("offset", UInt(*offset as u64)),
+				("size", UInt(data.len() as u64)),
+			],
+			Self::SetViewport { first, viewports } => vec![
+				("first", UInt(*first as u64)),
+				(
+					"viewports",
+					List(
+						viewports
+							.iter()
+							.map(|viewport| Ident(format!("{:?}", viewport)))
+							.collect(),
+					),
+				),
+			],
+			Self::SetScissors { first, scissors } => vec![
+				("first", UInt(*first as u64)),
+				(
+					"scissors",
+					List(
+						scissors
+							.iter()
+							.map(|scissor| Ident(format!("{:?}", scissor)))
+							.collect(),
+					),
+				),
+			],
+			Self::SetLineWidth(width) => vec![("width", Float(*width as f64))],
+			Self::SetDepthBias {
+				constant_factor,
+				clamp,
+				slope_factor,
+			} => vec![
+				("constant_factor", Float(*constant_factor as f64)),
+				("clamp", Float(*clamp as f64)),
+				("slope_factor", Float(*slope_factor as f64)),
+			],
+			Self::SetBlendConstants(constants) => vec![("constants", floats(constants))],
+			Self::SetStencilReference { faces, reference } => vec![
+				("faces", Ident(format!("{:?}", faces))),
+				("reference", UInt(*reference as u64)),
+			],
+			Self::SetDepthBounds { min, max } => {
+				vec![("min", Float(*min as f64)), ("max", Float(*max as f64))]
+			}
+			Self::SetStencilCompareMask { faces, mask }
+			| Self::SetStencilWriteMask { faces, mask } => vec![
+				("faces", Ident(format!("{:?}", faces))),
+				("mask", UInt(*mask as u64)),
+			],
+			Self::SetCullMode(cull_mode) => vec![("cull_mode", Ident(format!("{:?}", cull_mode)))],
+			Self::SetFrontFace(front_face) => {
+				vec![("front_face", Ident(format!("{:?}", front_face)))]
+			}
+			Self::SetPrimitiveTopology(topology) => {
+				vec![("topology", Ident(format!("{:?}", topology)))]
+			}
+			Self::SetDepthTestEnable(enabled)
+			| Self::SetDepthWriteEnable(enabled)
+			| Self::SetDepthBoundsTestEnable(enabled)
+			| Self::SetStencilTestEnable(enabled) => vec![("enabled", Bool(*enabled))],
+			Self::SetDepthCompareOp(op) => vec![("op", Ident(format!("{:?}", op)))],
+			Self::SetStencilOp {
+				faces,
+				fail,
+				pass,
+				depth_fail,
+				compare,
+			} => vec![
+				("faces", Ident(format!("{:?}", faces))),
+				("fail", Ident(format!("{:?}", fail))),
+				("pass", Ident(format!("{:?}", pass))),
+				("depth_fail", Ident(format!("{:?}", depth_fail))),
+				("compare", Ident(format!("{:?}", compare))),
+			],
+			Self::BindVertexBuffers {
+				first_binding,
+				buffers,
+				offsets,
+			} => vec![
+				("first_binding", UInt(*first_binding as u64)),
+				(
+					"buffers",
+					List(buffers.iter().map(|buffer| named(&**buffer)).collect()),
+				),
+				(
+					"offsets",
+					List(offsets.iter().map(|offset| UInt(*offset)).collect()),
+				),
+			],
+			Self::BindIndexBuffer { buffer, offset } => {
+				vec![("buffer", named(&**buffer)), ("offset", UInt(*offset))]
+			}
+			Self::Draw {
+				index_count,
+				first_index,
+				instance_count,
+				first_instance,
+				vertex_offset,
+			} => vec![
+				("index_count", UInt(*index_count as u64)),
+				("first_index", UInt(*first_index as u64)),
+				("instance_count", UInt(*instance_count as u64)),
+				("first_instance", UInt(*first_instance as u64)),
+				("vertex_offset", UInt(*vertex_offset as u64)),
+			],
+			Self::DrawVertices {
+				vertex_count,
+				first_vertex,
+				instance_count,
+				first_instance,
+			} => vec![
+				("vertex_count", UInt(*vertex_count as u64)),
+				("first_vertex", UInt(*first_vertex as u64)),
+				("instance_count", UInt(*instance_count as u64)),
+				("first_instance", UInt(*first_instance as u64)),
+			],
+			Self::DrawIndirect { draws, draw_count }
+			| Self::DrawIndexedIndirect { draws, draw_count } => {
+				let mut args = draws.args();
+				args.push(("draw_count", UInt(*draw_count as u64)));
+				args
+			}
+			Self::DrawIndirectCount {
+				draws,
+				count_buffer,
+				count_offset,
+				max_draw_count,
+			}
+			| Self::DrawIndexedIndirectCount {
+				draws,
+				count_buffer,
+				count_offset,
+				max_draw_count,
+			} => {
+				let mut args = draws.args();
+				args.push(("count_buffer", named(&**count_buffer)));
+				args.push(("count_offset", UInt(*count_offset as u64)));
+				args.push(("max_draw_count", UInt(*max_draw_count as u64)));
+				args
+			}
+			Self::Dispatch { group_counts } => vec![("group_counts", uints(group_counts))],
+			Self::DispatchBase {
+				base_group,
+				group_counts,
+			} => vec![
+				("base_group", uints(base_group)),
+				("group_counts", uints(group_counts)),
+			],
+			Self::DispatchIndirect { buffer, offset } => vec![
+				("buffer", named(&**buffer)),
+				("offset", UInt(*offset as u64)),
+			],
+			Self::ResetQueries { pool, queries } => vec![
+				("pool", named(&**pool)),
+				("first", UInt(queries.start as u64)),
+				(
+					"count",
+					UInt(queries.end.saturating_sub(queries.start) as u64),
+				),
+			],
+			Self::BeginQuery {
+				pool,
+				index,
+				precise,
+			} => vec![
+				("pool", named(&**pool)),
+				("index", UInt(*index as u64)),
+				("precise", Bool(*precise)),
+			],
+			Self::EndQuery { pool, index } => {
+				vec![("pool", named(&**pool)), ("index", UInt(*index as u64))]
+			}
+			Self::WriteTimestamp { pool, index, stage } => vec![
+				("pool", named(&**pool)),
+				("index", UInt(*index as u64)),
+				("stage", Ident(stage.to_string())),
+			],
+		}
+	}
+
+	/// Records the command into a buffer.
+	pub fn encode(&self, buffer: &mut command::Buffer) -> utility::Result<()> {
+		match self {
+			Self::BeginLabel { name, color } => buffer.begin_label(name.clone(), *color),
+			Self::InsertLabel { name, color } => buffer.insert_label(name.clone(), *color),
+			Self::EndLabel => buffer.end_label(),
+			Self::PipelineBarrier(barrier) => buffer.mark_pipeline_barrier(barrier.clone()),
+			Self::SetEvent { event, barrier } => buffer.set_event(event, barrier),
+			Self::WaitEvents(events) => buffer.wait_events(
+				events
+					.iter()
+					.map(|(event, barrier)| (event, barrier.clone()))
+					.collect(),
+			),
+			Self::ResetEvent { event, stages } => buffer.reset_event(event, *stages),
+			Self::Transition(transitions) => buffer.transition_all(transitions.clone())?,
+			Self::ReleaseOwnership(transfer) => buffer.release_ownership(transfer),
+			Self::AcquireOwnership(transfer) => buffer.acquire_ownership(transfer),
+			Self::CopyBufferToBuffer { src, dst, regions } => {
+				buffer.copy_buffer_to_buffer(src, dst, regions.clone())
+			}
+			Self::FillBuffer {
+				buffer: dst,
+				offset,
+				size,
+				data,
+			} => match size {
+				Some(size) => buffer.fill_buffer(dst, *offset..*offset + *size, *data)?,
+				None => buffer.fill_buffer(dst, *offset.., *data)?,
+			},
+			Self::UpdateBuffer {
+				buffer: dst,
+				offset,
+				data,
+			} => buffer.update_buffer_bytes(dst, *offset, &data[..])?,
+			Self::CopyBufferToImage {
+				buffer: src,
+				image,
+				layout,
+				regions,
+			} => buffer.copy_buffer_to_image(src, image, *layout, regions.clone()),
+			Self::CopyImageToBuffer {
+				image,
+				layout,
+				buffer: dst,
+				regions,
+			} => buffer.copy_image_to_buffer(image, *layout, dst, regions.clone())?,
+			Self::CopyImageToImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+			} => buffer.copy_image_to_image(src, *src_layout, dst, *dst_layout, regions.clone())?,
+			Self::BlitImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+				filter,
+			} => buffer.blit_image(src, *src_layout, dst, *dst_layout, regions.clone(), *filter)?,
+			Self::ResolveImage {
+				src,
+				src_layout,
+				dst,
+				dst_layout,
+				regions,
+			} => buffer.resolve_image(src, *src_layout, dst, *dst_layout, regions.clone())?,
+			Self::ClearImage {
+				image,
+				layout,
+				value,
+				ranges,
+			} => buffer.clear_image(image, *layout, *value, ranges.clone())?,
+			Self::StartRenderPass {
+				frame_buffer,
+				render_pass,
+				instruction,
+				uses_secondary_buffers,
+			} => buffer.start_render_pass(
+				frame_buffer,
+				render_pass,
+				instruction.clone(),
+				*uses_secondary_buffers,
+			),
+			Self::NextSubpass {
+				uses_secondary_buffers,
+			} => buffer.next_subpass(*uses_secondary_buffers),
+			Self::StopRenderPass => buffer.stop_render_pass(),
+			Self::Execute(secondaries) => {
+				buffer.execute(secondaries.iter().map(|secondary| &**secondary))
+			}
+			Self::ClearAttachments { attachments, rects } => {
+				buffer.clear_attachments(attachments.clone(), rects.clone())?
+			}
+			Self::BindPipeline {
+				pipeline,
+				bind_point,
+			} => buffer.bind_pipeline(pipeline, *bind_point),
+			Self::BindDescriptors {
+				bind_point,
+				layout,
+				first_set_index,
+				sets,
+			} => buffer.bind_descriptors(
+				*bind_point,
+				layout,
+				*first_set_index,
+				sets.iter().map(|set| &**set).collect(),
+			),
+			Self::PushConstant {
+				layout,
+				stages,
+				offset,
+				data,
+			} => buffer.push_constant_bytes(layout, *stages, *offset, &data[..]),
+			Self::SetViewport { first, viewports } => {
+				buffer.set_dynamic_viewport(*first, viewports.clone())
+			}
+			Self::SetScissors { first, scissors } => {
+				buffer.set_dynamic_scissors(*first, scissors.clone())
+			}
+			Self::SetLineWidth(width) => buffer.set_dynamic_line_width(*width),
+			Self::SetDepthBias {
+				constant_factor,
+				clamp,
+				slope_factor,
+			} => buffer.set_dynamic_depth_bias(*constant_factor, *clamp, *slope_factor),
+			Self::SetBlendConstants(constants) => buffer.set_dynamic_blend_constants(*constants),
+			Self::SetStencilReference { faces, reference } => {
+				buffer.set_dynamic_stencil_reference(*faces, *reference)
+			}
+			Self::SetDepthBounds { min, max } => buffer.set_dynamic_depth_bounds(*min, *max),
+			Self::SetStencilCompareMask { faces, mask } => {
+				buffer.set_dynamic_stencil_compare_mask(*faces, *mask)
+			}
+			Self::SetStencilWriteMask { faces, mask } => {
+				buffer.set_dynamic_stencil_write_mask(*faces, *mask)
+			}
+			Self::SetCullMode(cull_mode) => buffer.set_dynamic_cull_mode(*cull_mode)?,
+			Self::SetFrontFace(front_face) => buffer.set_dynamic_front_face(*front_face)?,
+			Self::SetPrimitiveTopology(topology) => {
+				buffer.set_dynamic_primitive_topology(*topology)?
+			}
+			Self::SetDepthTestEnable(enabled) => buffer.set_dynamic_depth_test_enable(*enabled)?,
+			Self::SetDepthWriteEnable(enabled) => {
+				buffer.set_dynamic_depth_write_enable(*enabled)?
+			}
+			Self::SetDepthCompareOp(op) => buffer.set_dynamic_depth_compare_op(*op)?,
+			Self::SetDepthBoundsTestEnable(enabled) => {
+				buffer.set_dynamic_depth_bounds_test_enable(*enabled)?
+			}
+			Self::SetStencilTestEnable(enabled) => {
+				buffer.set_dynamic_stencil_test_enable(*enabled)?
+			}
+			Self::SetStencilOp {
+				faces,
+				fail,
+				pass,
+				depth_fail,
+				compare,
+			} => buffer.set_dynamic_stencil_op(*faces, *fail, *pass, *depth_fail, *compare)?,
+			Self::BindVertexBuffers {
+				first_binding,
+				buffers,
+				offsets,
+			} => buffer.bind_vertex_buffers(
+				*first_binding,
+				buffers.iter().collect(),
+				offsets.clone(),
+			),
+			Self::BindIndexBuffer {
+				buffer: index_buffer,
+				offset,
+			} => buffer.bind_index_buffer(index_buffer, *offset),
+			Self::Draw {
+				index_count,
+				first_index,
+				instance_count,
+				first_instance,
+				vertex_offset,
+			} => buffer.draw(
+				*index_count,
+				*first_index,
+				*instance_count,
+				*first_instance,
+				*vertex_offset,
+			),
+			Self::DrawVertices {
+				vertex_count,
+				first_vertex,
+				instance_count,
+				first_instance,
+			} => buffer.draw_vertices(
+				*vertex_count,
+				*first_vertex,
+				*instance_count,
+				*first_instance,
+			),
+			Self::DrawIndirect { draws, draw_count } => {
+				buffer.draw_indirect(draws.as_command(), *draw_count)?
+			}
+			Self::DrawIndexedIndirect { draws, draw_count } => {
+				buffer.draw_indexed_indirect(draws.as_command(), *draw_count)?
+			}
+			Self::DrawIndirectCount {
+				draws,
+				count_buffer,
+				count_offset,
+				max_draw_count,
+			} => buffer.draw_indirect_count(
+				draws.as_command(),
+				count_buffer,
+				*count_offset,
+				*max_draw_count,
+			)?,
+			Self::DrawIndexedIndirectCount {
+				draws,
+				count_buffer,
+				count_offset,
+				max_draw_count,
+			} => buffer.draw_indexed_indirect_count(
+				draws.as_command(),
+				count_buffer,
+				*count_offset,
+				*max_draw_count,
+			)?,
+			Self::Dispatch { group_counts } => buffer.dispatch(*group_counts),
+			Self::DispatchBase {
+				base_group,
+				group_counts,
+			} => buffer.dispatch_base(*base_group, *group_counts),
+			Self::DispatchIndirect {
+				buffer: indirect,
+				offset,
+			} => buffer.dispatch_indirect(indirect, *offset)?,
+			Self::ResetQueries { pool, queries } => buffer.reset_queries(pool, queries.clone())?,
+			Self::BeginQuery {
+				pool,
+				index,
+				precise,
+			} => buffer.begin_query(pool, *index, *precise)?,
+			Self::EndQuery { pool, index } => buffer.end_query(pool, *index)?,
+			Self::WriteTimestamp { pool, index, stage } => {
+				buffer.write_timestamp(pool, *index, *stage)?
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the command as a single line of text (i.e. `draw_vertices vertex_count=3 ...`).
+	pub fn to_text(&self) -> String {
+		let mut text = self.name().to_owned();
+		for (key, value) in self.args() {
+			text.push(' ');
+			text.push_str(key);
+			text.push('=');
+			value.write_text(&mut text);
+		}
+		text
+	}
+
+	/// Returns the command as a JSON object, with the command's name in the `"command"` field.
+	pub fn to_json(&self) -> String {
+		let mut json = format!("{{\"command\":{}", json_string(self.name()));
+		for (key, value) in self.args() {
+			json.push(',');
+			json.push_str(&json_string(key));
+			json.push(':');
+			value.write_json(&mut json);
+		}
+		json.push('}');
+		json
+	}
+}
+
+/// An owned [`command::IndirectDraws`], which keeps the buffer of draw commands alive.
+#[derive(Clone)]
+pub struct IndirectDraws {
+	pub buffer: Arc<buffer::Buffer>,
+	pub offset: usize,
+	pub stride: usize,
+}
+
+impl IndirectDraws {
+	fn as_command(&self) -> command::IndirectDraws<'_> {
+		command::IndirectDraws {
+			buffer: &self.buffer,
+			offset: self.offset,
+			stride: self.stride,
+		}
+	}
+
+	fn args(&self) -> Vec<(&'static str, Arg)> {
+		vec![
+			("buffer", named(&*self.buffer)),
+			("offset", Arg::UInt(self.offset as u64)),
+			("stride", Arg::UInt(self.stride as u64)),
+		]
+	}
+}
+
+/// A list of [`Commands`](Command) which is recorded without a GPU, and [`encoded`](List::encode)
+/// into a real command buffer later.
+///
+/// The commands can be inspected directly (i.e. to assert which commands a renderer issues in a unit test),
+/// or dumped with [`to_text`](List::to_text) and [`to_json`](List::to_json).
+/// Lists hold the resources their commands use, and can be sent to a worker thread for encoding.
+#[derive(Clone, Default)]
+pub struct List {
+	name: String,
+	commands: Vec<Command>,
+}
+
+impl List {
+	pub fn new<TStr>(name: TStr) -> Self
+	where
+		TStr: Into<String>,
+	{
+		Self {
+			name: name.into(),
+			commands: Vec::new(),
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Adds a command to the end of the list.
+	pub fn push(&mut self, command: Command) -> &mut Self {
+		self.commands.push(command);
+		self
+	}
+
+	pub fn commands(&self) -> &Vec<Command> {
+		&self.commands
+	}
+
+	pub fn len(&self) -> usize {
+		self.commands.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.commands.is_empty()
+	}
+
+	pub fn clear(&mut self) {
+		self.commands.clear();
+	}
+
+	/// Records each command, in order, into a buffer which has been [`begun`](command::Buffer::begin).
+	/// The list is left untouched, so it can be encoded again into other buffers.
+	///
+	/// Stops at the first command which fails, returning its error.
+	#[profiling::function]
+	pub fn encode(&self, buffer: &mut command::Buffer) -> utility::Result<()> {
+		for command in self.commands.iter() {
+			command.encode(buffer)?;
+		}
+		Ok(())
+	}
+
+	/// Returns each command on its own line, see [`Command::to_text`].
+	pub fn to_text(&self) -> String {
+		self.commands
+			.iter()
+			.map(|command| command.to_text())
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+
+	/// Returns the list as a JSON object with its `"name"` and an array of `"commands"`, see [`Command::to_json`].
+	pub fn to_json(&self) -> String {
+		format!(
+			"{{\"name\":{},\"commands\":[{}]}}",
+			json_string(&self.name),
+			self.commands
+				.iter()
+				.map(|command| command.to_json())
+				.collect::<Vec<_>>()
+				.join(",")
+		)
+	}
+}
+
+/// An argument of a command, as written into text or JSON.
+enum Arg {
+	UInt(u64),
+	Float(f64),
+	Bool(bool),
+	/// Quoted in both text and JSON.
+	Str(String),
+	/// Unquoted in text, quoted in JSON.
+	Ident(String),
+	List(Vec<Arg>),
+	/// Named arguments, i.e. the properties of a single barrier.
+	Fields(Vec<(&'static str, Arg)>),
+}
+
+impl Arg {
+	fn write_text(&self, out: &mut String) {
+		match self {
+			Self::UInt(value) => out.push_str(&value.to_string()),
+			Self::Float(value) => out.push_str(&value.to_string()),
+			Self::Bool(value) => out.push_str(&value.to_string()),
+			Self::Str(value) => out.push_str(&format!("{:?}", value)),
+			Self::Ident(value) => out.push_str(value),
+			Self::List(values) => {
+				out.push('[');
+				for (i, value) in values.iter().enumerate() {
+					if i > 0 {
+						out.push_str(", ");
+					}
+					value.write_text(out);
+				}
+				out.push(']');
+			}
+			Self::Fields(fields) => {
+				out.push('{');
+				for (i, (key, value)) in fields.iter().enumerate() {
+					if i > 0 {
+						out.push_str(", ");
+					}
+					out.push_str(key);
+					out.push('=');
+					value.write_text(out);
+				}
+				out.push('}');
+			}
+		}
+	}
+
+	fn write_json(&self, out: &mut String) {
+		match self {
+			Self::UInt(value) => out.push_str(&value.to_string()),
+			Self::Float(value) if value.is_finite() => out.push_str(&value.to_string()),
+			Self::Float(_) => out.push_str("null"),
+			Self::Bool(value) => out.push_str(&value.to_string()),
+			Self::Str(value) | Self::Ident(value) => out.push_str(&json_string(value)),
+			Self::List(values) => {
+				out.push('[');
+				for (i, value) in values.iter().enumerate() {
+					if i > 0 {
+						out.push(',');
+					}
+					value.write_json(out);
+				}
+				out.push(']');
+			}
+			Self::Fields(fields) => {
+				out.push('{');
+				for (i, (key, value)) in fields.iter().enumerate() {
+					if i > 0 {
+						out.push(',');
+					}
+					out.push_str(&json_string(key));
+					out.push(':');
+					value.write_json(out);
+				}
+				out.push('}');
+			}
+		}
+	}
+}
+
+fn barrier_args(barrier: &barrier::Pipeline) -> Vec<(&'static str, Arg)> {
+	vec![
+		(
+			"src_stages",
+			Arg::Ident(format!("{:?}", barrier.src_stages.as_vk2())),
+		),
+		(
+			"dst_stages",
+			Arg::Ident(format!("{:?}", barrier.dst_stages.as_vk2())),
+		),
+		(
+			"barriers",
+			Arg::List(barrier.kinds.iter().map(barrier_kind_arg).collect()),
+		),
+	]
+}
+
+fn barrier_kind_arg(kind: &barrier::Kind) -> Arg {
+	use Arg::*;
+	let mut fields = match kind {
+		barrier::Kind::Memory(memory) => vec![
+			("kind", Ident("memory".to_owned())),
+			("src_access", access(memory.src_access)),
+			("dst_access", access(memory.dst_access)),
+		],
+		barrier::Kind::Buffer(buffer) => vec![
+			("kind", Ident("buffer".to_owned())),
+			("buffer", named(&*buffer.buffer)),
+			("offset", UInt(buffer.offset as u64)),
+			(
+				"size",
+				buffer
+					.size
+					.map_or(Ident("whole".to_owned()), |size| UInt(size as u64)),
+			),
+			("src_access", access(buffer.src_access)),
+			("dst_access", access(buffer.dst_access)),
+		],
+		barrier::Kind::Image(image) => vec![
+			("kind", Ident("image".to_owned())),
+			(
+				"image",
+				image
+					.image
+					.upgrade()
+					.map_or(Ident("<dropped>".to_owned()), |image| named(&*image)),
+			),
+			("range", Ident(format!("{:?}", image.range))),
+			("old_layout", Ident(format!("{:?}", image.old_layout))),
+			("new_layout", Ident(format!("{:?}", image.new_layout))),
+			("src_access", access(image.src_access)),
+			("dst_access", access(image.dst_access)),
+		],
+	};
+	let queue_families = match kind {
+		barrier::Kind::Memory(_) => None,
+		barrier::Kind::Buffer(buffer) => Some((buffer.src_queue_family, buffer.dst_queue_family)),
+		barrier::Kind::Image(image) => Some((image.src_queue_family, image.dst_queue_family)),
+	};
+	if let Some((src, dst)) = queue_families {
+		if src != backend::vk::QUEUE_FAMILY_IGNORED {
+			fields.push(("src_queue_family", UInt(src as u64)));
+			fields.push(("dst_queue_family", UInt(dst as u64)));
+		}
+	}
+	Fields(fields)
+}
+
+fn transition_arg(transition: &barrier::Transition) -> Arg {
+	use Arg::*;
+	Fields(match transition {
+		barrier::Transition::Image { image, range, next } => vec![
+			("image", named(&**image)),
+			("range", Ident(format!("{:?}", range))),
+			("layout", Ident(format!("{:?}", next.layout))),
+			(
+				"stages",
+				Ident(format!("{:?}", next.access.stages.as_vk2())),
+			),
+			("access", access(next.access.access)),
+		],
+		barrier::Transition::Buffer { buffer, next } => vec![
+			("buffer", named(&**buffer)),
+			("stages", Ident(format!("{:?}", next.stages.as_vk2()))),
+			("access", access(next.access)),
+		],
+	})
+}
+
+fn access(access: flags::AccessSet) -> Arg {
+	Arg::Ident(format!("{:?}", access.as_vk2()))
+}
+
+fn uints(values: &[u32]) -> Arg {
+	Arg::List(
+		values
+			.iter()
+			.map(|value| Arg::UInt(*value as u64))
+			.collect(),
+	)
+}
+
+fn debug_list<T: std::fmt::Debug>(values: &[T]) -> Arg {
+	Arg::List(
+		values
+			.iter()
+			.map(|value| Arg::Ident(format!("{:?}", value)))
+			.collect(),
+	)
+}
+
+fn floats(values: &[f32]) -> Arg {
+	Arg::List(
+		values
+			.iter()
+			.map(|value| Arg::Float(*value as f64))
+			.collect(),
+	)
+}
+
+fn named(object: &impl NamedObject) -> Arg {
+	Arg::Str(object.name().clone())
+}
+
+fn handle(object: &impl HandledObject) -> Arg {
+	Arg::Ident(format!("{:#x}", object.handle()))
+}
+
+fn shader_stages(stages: EnumSet<flags::ShaderKind>) -> Arg {
+	Arg::Ident(
+		stages
+			.iter()
+			.map(|stage| stage.into())
+			.collect::<Vec<String>>()
+			.join("|"),
+	)
+}
+
+fn json_string(value: &str) -> String {
+	let mut json = String::with_capacity(value.len() + 2);
+	json.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\r' => json.push_str("\\r"),
+			'\t' => json.push_str("\\t"),
+			c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+			c => json.push(c),
+		}
+	}
+	json.push('"');
+	json
+}
+
+#[cfg(test)]
+mod dump {
+	use super::*;
+
+	fn list() -> List {
+		let mut list = List::new("Frame");
+		list.push(Command::BeginLabel {
+			name: "Post \"fx\"".to_owned(),
+			color: [1.0, 0.5, 0.0, 1.0],
+		})
+		.push(Command::SetLineWidth(2.0))
+		.push(Command::DrawVertices {
+			vertex_count: 3,
+			first_vertex: 0,
+			instance_count: 1,
+			first_instance: 0,
+		})
+		.push(Command::Dispatch {
+			group_counts: [8, 4, 1],
+		})
+		.push(Command::EndLabel);
+		list
+	}
+
+	#[test]
+	fn commands_can_be_inspected() {
+		let list = list();
+		let names = list
+			.commands()
+			.iter()
+			.map(|command| command.name())
+			.collect::<Vec<_>>();
+		assert_eq!(
+			names,
+			vec![
+				"begin_label",
+				"set_line_width",
+				"draw_vertices",
+				"dispatch",
+				"end_label"
+			]
+		);
+		assert!(matches!(
+			list.commands()[2],
+			Command::DrawVertices {
+				vertex_count: 3,
+				..
+			}
+		));
+	}
+
+	#[test]
+	fn text() {
+		assert_eq!(
+			list().to_text(),
+			"begin_label name=\"Post \\\"fx\\\"\" color=[1, 0.5, 0, 1]\n\
+			set_line_width width=2\n\
+			draw_vertices vertex_count=3 first_vertex=0 instance_count=1 first_instance=0\n\
+			dispatch group_counts=[8, 4, 1]\n\
+			end_label"
+		);
+	}
+
+	#[test]
+	fn json() {
+		assert_eq!(
+			list().to_json(),
+			"{\"name\":\"Frame\",\"commands\":[\
+			{\"command\":\"begin_label\",\"name\":\"Post \\\"fx\\\"\",\"color\":[1,0.5,0,1]},\
+			{\"command\":\"set_line_width\",\"width\":2},\
+			{\"command\":\"draw_vertices\",\"vertex_count\":3,\"first_vertex\":0,\"instance_count\":1,\"first_instance\":0},\
+			{\"command\":\"dispatch\",\"group_counts\":[8,4,1]},\
+			{\"command\":\"end_label\"}]}"
+		);
+	}
+
+	#[test]
+	fn barriers_include_their_access_masks() {
+		let barrier = barrier::Pipeline::new(
+			flags::PipelineStage::ComputerShader,
+			flags::PipelineStage::VertexShader,
+		)
+		.with(barrier::Kind::Memory(
+			barrier::Memory::default()
+				.requires(flags::Access::ShaderWrite)
+				.prevents(flags::Access::ShaderRead),
+		));
+		let command = Command::PipelineBarrier(barrier);
+		assert_eq!(
+			command.to_text(),
+			"pipeline_barrier src_stages=COMPUTE_SHADER dst_stages=VERTEX_SHADER \
+			barriers=[{kind=memory, src_access=SHADER_WRITE, dst_access=SHADER_READ}]"
+		);
+		assert_eq!(
+			command.to_json(),
+			"{\"command\":\"pipeline_barrier\",\"src_stages\":\"COMPUTE_SHADER\",\"dst_stages\":\"VERTEX_SHADER\",\
+			\"barriers\":[{\"kind\":\"memory\",\"src_access\":\"SHADER_WRITE\",\"dst_access\":\"SHADER_READ\"}]}"
+		);
+	}
+
+	#[test]
+	fn dynamic_state() {
+		let mut list = List::new("State");
+		list.push(Command::SetDepthBounds { min: 0.0, max: 1.0 })
+			.push(Command::SetCullMode(flags::CullMode::BACK))
+			.push(Command::SetDepthTestEnable(true))
+			.push(Command::DispatchBase {
+				base_group: [1, 0, 0],
+				group_counts: [2, 2, 1],
+			});
+		assert_eq!(
+			list.to_text(),
+			"set_depth_bounds min=0 max=1\n\
+			set_cull_mode cull_mode=BACK\n\
+			set_depth_test_enable enabled=true\n\
+			dispatch_base base_group=[1, 0, 0] group_counts=[2, 2, 1]"
+		);
+	}
+
+	#[test]
+	fn lists_can_be_sent_to_other_threads() {
+		fn assert_send<T: Send>() {}
+		assert_send::<List>();
+	}
+}
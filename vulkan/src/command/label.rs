@@ -0,0 +1,41 @@
+use crate::command::Buffer;
+
+/// A debug label which is ended when the scope is dropped,
+/// created by [`Buffer::label_scope`] or [`Recorder::label_scope`](crate::command::Recorder::label_scope).
+///
+/// Commands are recorded through the scope, which dereferences to the buffer (or recorder) it was created from:
+/// ```ignore
+/// let mut shadows = buffer.label_scope("Shadows", [0.2, 0.2, 0.2, 1.0]);
+/// shadows.draw(index_count, 0, 1, 0, 0);
+/// ```
+#[must_use]
+pub struct LabelScope<'a, T> {
+	target: &'a mut T,
+	buffer: fn(&mut T) -> &mut Buffer,
+}
+
+impl<'a, T> LabelScope<'a, T> {
+	/// Creates the scope for a label which has already been begun on the buffer returned by `buffer`.
+	pub(crate) fn new(target: &'a mut T, buffer: fn(&mut T) -> &mut Buffer) -> Self {
+		Self { target, buffer }
+	}
+}
+
+impl<'a, T> std::ops::Deref for LabelScope<'a, T> {
+	type Target = T;
+	fn deref(&self) -> &Self::Target {
+		self.target
+	}
+}
+
+impl<'a, T> std::ops::DerefMut for LabelScope<'a, T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.target
+	}
+}
+
+impl<'a, T> Drop for LabelScope<'a, T> {
+	fn drop(&mut self) {
+		(self.buffer)(self.target).end_label();
+	}
+}
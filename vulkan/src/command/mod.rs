@@ -1,21 +1,62 @@
 pub mod barrier;
 
+mod bind_state;
+pub use bind_state::*;
+
 mod buffer;
 pub use buffer::*;
 
+mod completion;
+pub use completion::*;
+
+mod event;
+pub use event::*;
+
 pub mod frame;
 
+pub mod ir;
+
+mod label;
+pub use label::*;
+
+mod op_clear;
+pub use op_clear::*;
+
 mod op_copy;
 pub use op_copy::*;
 
+mod op_dispatch;
+pub use op_dispatch::*;
+
+mod op_draw;
+pub use op_draw::*;
+
+mod op_fill;
+pub use op_fill::*;
+
 mod op_present;
 pub use op_present::*;
 
+mod ownership;
+pub use ownership::*;
+
 mod op_submit;
 pub use op_submit::*;
 
+mod parallel;
+pub use parallel::*;
+
 mod pool;
 pub use pool::*;
 
+mod recording;
+pub use recording::*;
+
+mod submission;
+pub use submission::*;
+
+mod sync_pool;
+pub use sync_pool::*;
+
 mod syncing;
 pub use syncing::*;
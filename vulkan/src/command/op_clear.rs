@@ -0,0 +1,54 @@
+use crate::{flags::ImageAspect, structs::Rect2D};
+
+/// An attachment of the current subpass that is cleared by
+/// [`clear_attachments`](crate::command::Buffer::clear_attachments).
+#[derive(Clone, Copy, Debug)]
+pub enum ClearAttachment {
+	/// Clears the color attachment at an index in the current subpass's color attachments.
+	Color(usize, [f32; 4]),
+	/// Clears the depth and/or stencil aspects of the current subpass's depth-stencil attachment.
+	DepthStencil(ImageAspect, f32, u32),
+}
+
+/// An area of the attachments (and which array layers)
+/// cleared by [`clear_attachments`](crate::command::Buffer::clear_attachments).
+#[derive(Clone, Debug)]
+pub struct ClearRect {
+	/// The 2D region of the attachments to clear.
+	pub area: Rect2D,
+	/// The array layers of the attachments to clear.
+	pub layers: std::ops::Range<u32>,
+}
+
+impl ClearRect {
+	/// The number of layers to clear, or an error if the range of layers is empty or inverted.
+	pub(crate) fn layer_count(&self) -> Result<u32, String> {
+		match self.layers.end.checked_sub(self.layers.start) {
+			Some(count) if count > 0 => Ok(count),
+			_ => Err(format!(
+				"clear rect layers {:?} must contain at least one layer",
+				self.layers
+			)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod clear_rect {
+	use super::*;
+
+	fn rect(layers: std::ops::Range<u32>) -> ClearRect {
+		ClearRect {
+			area: Rect2D::default(),
+			layers,
+		}
+	}
+
+	#[test]
+	#[allow(clippy::reversed_empty_ranges)]
+	fn layer_counts() {
+		assert_eq!(rect(2..5).layer_count(), Ok(3));
+		assert!(rect(2..2).layer_count().is_err());
+		assert!(rect(5..2).layer_count().is_err());
+	}
+}
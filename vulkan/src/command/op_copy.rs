@@ -5,6 +5,7 @@ use crate::structs::{subresource, Extent3D, Offset3D};
 /// to an area of an [`image`](crate::image::Image).
 ///
 /// Used in conjunction with [`copy_buffer_to_image`](crate::command::Buffer::copy_buffer_to_image).
+#[derive(Clone, Debug)]
 pub struct CopyBufferToImage {
 	/// The offset from the start of the buffer to read data from.
 	pub buffer_offset: usize,
@@ -16,11 +17,69 @@ pub struct CopyBufferToImage {
 	pub size: Extent3D,
 }
 
+/// Properties used to copy
+/// an area of an [`image`](crate::image::Image)
+/// to a portion of a [`buffer`](crate::buffer::Buffer).
+///
+/// Used in conjunction with [`copy_image_to_buffer`](crate::command::Buffer::copy_image_to_buffer).
+#[derive(Clone, Debug)]
+pub struct CopyImageToBuffer {
+	/// The image's subresource layers.
+	pub layers: subresource::Layers,
+	/// The offset from the start of each dimension of the image to read from.
+	pub offset: Offset3D,
+	/// The size of the image segment to read from.
+	pub size: Extent3D,
+	/// The offset from the start of the buffer to write data to.
+	pub buffer_offset: usize,
+}
+
+/// Properties used to copy
+/// an area of an [`image`](crate::image::Image)
+/// to an area of another [`image`](crate::image::Image).
+///
+/// Used in conjunction with [`copy_image_to_image`](crate::command::Buffer::copy_image_to_image)
+/// and [`resolve_image`](crate::command::Buffer::resolve_image).
+#[derive(Clone, Debug)]
+pub struct CopyImage {
+	/// The source image's subresource layers.
+	pub src_layers: subresource::Layers,
+	/// The offset from the start of each dimension of the source image to read from.
+	pub src_offset: Offset3D,
+	/// The destination image's subresource layers.
+	pub dst_layers: subresource::Layers,
+	/// The offset from the start of each dimension of the destination image to write to.
+	pub dst_offset: Offset3D,
+	/// The size of the image segment to copy.
+	pub size: Extent3D,
+}
+
+/// Properties used to copy and scale
+/// an area of an [`image`](crate::image::Image)
+/// to an area of another [`image`](crate::image::Image).
+///
+/// The bounds of each area are the start (inclusive) and end (exclusive) corners of the area.
+/// If the corners of the source and destination are in different orders, the area is mirrored.
+///
+/// Used in conjunction with [`blit_image`](crate::command::Buffer::blit_image).
+#[derive(Clone, Debug)]
+pub struct BlitImage {
+	/// The source image's subresource layers.
+	pub src_layers: subresource::Layers,
+	/// The corners of the area in the source image to read from.
+	pub src_bounds: [Offset3D; 2],
+	/// The destination image's subresource layers.
+	pub dst_layers: subresource::Layers,
+	/// The corners of the area in the destination image to write to.
+	pub dst_bounds: [Offset3D; 2],
+}
+
 /// Properties used to copy
 /// a portion of a [`buffer`](crate::buffer::Buffer)
 /// to a portion of another [`buffer`](crate::buffer::Buffer).
 ///
 /// Used in conjunction with [`copy_buffer_to_buffer`](crate::command::Buffer::copy_buffer_to_buffer).
+#[derive(Clone, Copy)]
 pub struct CopyBufferRange {
 	/// The byte offset from the start of the source buffer.
 	pub start_in_src: usize,
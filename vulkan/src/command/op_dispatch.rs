@@ -0,0 +1,16 @@
+/// The number of workgroups in a single compute dispatch, as read by the GPU from an indirect buffer.
+///
+/// Written into a [`buffer`](crate::buffer::Buffer) (i.e. through [`alloc::Memory`](crate::alloc::Memory) or by a compute shader),
+/// and used in conjunction with [`dispatch_indirect`](crate::command::Buffer::dispatch_indirect).
+///
+/// Matches the layout of [`VkDispatchIndirectCommand`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkDispatchIndirectCommand.html).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DispatchIndirectCommand {
+	pub x: u32,
+	pub y: u32,
+	pub z: u32,
+}
+
+unsafe impl bytemuck::Zeroable for DispatchIndirectCommand {}
+unsafe impl bytemuck::Pod for DispatchIndirectCommand {}
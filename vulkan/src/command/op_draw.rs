@@ -0,0 +1,142 @@
+use crate::{
+	buffer, flags,
+	utility::{self, NamedObject},
+};
+use std::sync;
+
+/// The parameters of a single non-indexed draw, as read by the GPU from an indirect buffer.
+///
+/// Written into a [`buffer`](crate::buffer::Buffer) (i.e. through [`alloc::Memory`](crate::alloc::Memory) or by a compute shader),
+/// and used in conjunction with [`draw_indirect`](crate::command::Buffer::draw_indirect)
+/// and [`draw_indirect_count`](crate::command::Buffer::draw_indirect_count).
+///
+/// Matches the layout of [`VkDrawIndirectCommand`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkDrawIndirectCommand.html).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawIndirectCommand {
+	pub vertex_count: u32,
+	pub instance_count: u32,
+	pub first_vertex: u32,
+	pub first_instance: u32,
+}
+
+unsafe impl bytemuck::Zeroable for DrawIndirectCommand {}
+unsafe impl bytemuck::Pod for DrawIndirectCommand {}
+
+/// The parameters of a single indexed draw, as read by the GPU from an indirect buffer.
+///
+/// Written into a [`buffer`](crate::buffer::Buffer) (i.e. through [`alloc::Memory`](crate::alloc::Memory) or by a compute shader),
+/// and used in conjunction with [`draw_indexed_indirect`](crate::command::Buffer::draw_indexed_indirect)
+/// and [`draw_indexed_indirect_count`](crate::command::Buffer::draw_indexed_indirect_count).
+///
+/// Matches the layout of [`VkDrawIndexedIndirectCommand`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkDrawIndexedIndirectCommand.html).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawIndexedIndirectCommand {
+	pub index_count: u32,
+	pub instance_count: u32,
+	pub first_index: u32,
+	pub vertex_offset: i32,
+	pub first_instance: u32,
+}
+
+unsafe impl bytemuck::Zeroable for DrawIndexedIndirectCommand {}
+unsafe impl bytemuck::Pod for DrawIndexedIndirectCommand {}
+
+/// Where the commands for an indirect draw are read from.
+///
+/// Used in conjunction with the indirect draw commands of [`command::Buffer`](crate::command::Buffer).
+pub struct IndirectDraws<'a> {
+	/// The buffer containing the commands,
+	/// which must have been created with [`INDIRECT_BUFFER`](flags::BufferUsage::INDIRECT_BUFFER) usage.
+	pub buffer: &'a sync::Arc<buffer::Buffer>,
+	/// The offset, in bytes, of the first command in the buffer. Must be a multiple of 4.
+	pub offset: usize,
+	/// The number of bytes between the start of each command.
+	/// Must be a multiple of 4, and at least the size of the command if more than one command is drawn.
+	pub stride: usize,
+}
+
+impl<'a> IndirectDraws<'a> {
+	/// Reads tightly packed commands of type `T` from the start of the buffer.
+	pub fn packed<T: Sized>(buffer: &'a sync::Arc<buffer::Buffer>) -> Self {
+		Self {
+			buffer,
+			offset: 0,
+			stride: std::mem::size_of::<T>(),
+		}
+	}
+
+	/// Starts reading commands at an offset, in bytes, from the start of the buffer.
+	pub fn with_offset(mut self, offset: usize) -> Self {
+		self.offset = offset;
+		self
+	}
+
+	/// Returns an error if the commands of size `command_size` cannot be read from the buffer
+	/// with the offset and stride.
+	pub(crate) fn validate(
+		&self,
+		command_size: usize,
+		max_draw_count: usize,
+		action: &str,
+	) -> utility::Result<()> {
+		self.buffer
+			.require_usage(flags::BufferUsage::INDIRECT_BUFFER, action)?;
+		let error = |msg: String| {
+			Err(utility::Error::InvalidBufferUsage(format!(
+				"{:?} cannot be used for {}: {}",
+				self.buffer.name(),
+				action,
+				msg
+			)))
+		};
+		if !self.offset.is_multiple_of(4) {
+			return error(format!("offset {} is not a multiple of 4", self.offset));
+		}
+		if max_draw_count > 1 && (!self.stride.is_multiple_of(4) || self.stride < command_size) {
+			return error(format!(
+				"stride {} must be a multiple of 4 and at least {}",
+				self.stride, command_size
+			));
+		}
+		if max_draw_count > 0 {
+			let end = self.offset + self.stride * (max_draw_count - 1) + command_size;
+			if end > self.buffer.size() {
+				return error(format!(
+					"{} commands end at byte {}, past the end of the buffer ({} bytes)",
+					max_draw_count,
+					end,
+					self.buffer.size()
+				));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod indirect_layout {
+	use super::*;
+	use crate::backend::vk;
+
+	#[test]
+	fn matches_vulkan_layout() {
+		assert_eq!(
+			std::mem::size_of::<DrawIndirectCommand>(),
+			std::mem::size_of::<vk::DrawIndirectCommand>()
+		);
+		assert_eq!(
+			std::mem::size_of::<DrawIndexedIndirectCommand>(),
+			std::mem::size_of::<vk::DrawIndexedIndirectCommand>()
+		);
+		let command = DrawIndexedIndirectCommand {
+			index_count: 3,
+			vertex_offset: -1,
+			..Default::default()
+		};
+		let bytes = bytemuck::bytes_of(&command);
+		assert_eq!(&bytes[0..4], &3u32.to_ne_bytes());
+		assert_eq!(&bytes[12..16], &(-1i32).to_ne_bytes());
+	}
+}
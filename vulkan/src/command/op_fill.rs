@@ -0,0 +1,100 @@
+/// The largest number of bytes that [`update_buffer`](crate::command::Buffer::update_buffer) can write.
+/// Larger writes should be copied from a staging buffer instead.
+pub const MAX_UPDATE_BUFFER_SIZE: usize = 65536;
+
+/// Checks that `size` bytes (or the rest of the buffer if None) can be written into a buffer of `buffer_size` bytes at `offset`,
+/// by [`fill_buffer`](crate::command::Buffer::fill_buffer) or [`update_buffer`](crate::command::Buffer::update_buffer).
+/// `limit` is the largest size the command can write, if any.
+pub(crate) fn validate_buffer_write(
+	buffer_size: usize,
+	offset: usize,
+	size: Option<usize>,
+	limit: Option<usize>,
+) -> Result<(), String> {
+	if !offset.is_multiple_of(4) || offset >= buffer_size {
+		return Err(format!(
+			"offset {} must be a multiple of 4 and within the buffer ({} bytes)",
+			offset, buffer_size
+		));
+	}
+	if let Some(size) = size {
+		if size == 0 || !size.is_multiple_of(4) || offset + size > buffer_size {
+			return Err(format!(
+				"{} bytes at offset {} must be a non-zero multiple of 4 and fit within the buffer ({} bytes)",
+				size, offset, buffer_size
+			));
+		}
+		if let Some(limit) = limit {
+			if size > limit {
+				return Err(format!(
+					"{} bytes exceeds the limit of {} bytes, use a staging buffer and copy instead",
+					size, limit
+				));
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Returns the offset and size (None for the rest of the buffer) of a range of bytes
+/// passed to [`fill_buffer`](crate::command::Buffer::fill_buffer).
+pub(crate) fn fill_range(
+	range: impl std::ops::RangeBounds<usize>,
+) -> Result<(usize, Option<usize>), String> {
+	use std::ops::Bound;
+	let offset = match range.start_bound() {
+		Bound::Included(start) => Some(*start),
+		Bound::Excluded(start) => start.checked_add(1),
+		Bound::Unbounded => Some(0),
+	};
+	let end = match range.end_bound() {
+		Bound::Included(end) => end.checked_add(1).map(Some),
+		Bound::Excluded(end) => Some(Some(*end)),
+		Bound::Unbounded => Some(None),
+	};
+	match (offset, end) {
+		(Some(offset), Some(None)) => Ok((offset, None)),
+		(Some(offset), Some(Some(end))) => match end.checked_sub(offset) {
+			Some(size) => Ok((offset, Some(size))),
+			None => Err(format!("range {}..{} is inverted", offset, end)),
+		},
+		_ => Err("range overflows the addressable bytes".to_owned()),
+	}
+}
+
+#[cfg(test)]
+mod buffer_write {
+	use super::*;
+
+	#[test]
+	fn aligned_writes_within_the_buffer() {
+		assert!(validate_buffer_write(64, 0, Some(64), None).is_ok());
+		assert!(validate_buffer_write(64, 16, None, None).is_ok());
+		assert!(validate_buffer_write(64, 60, Some(4), Some(MAX_UPDATE_BUFFER_SIZE)).is_ok());
+	}
+
+	#[test]
+	fn misaligned_or_out_of_bounds() {
+		assert!(validate_buffer_write(64, 2, Some(4), None).is_err());
+		assert!(validate_buffer_write(64, 0, Some(6), None).is_err());
+		assert!(validate_buffer_write(64, 32, Some(64), None).is_err());
+		assert!(validate_buffer_write(64, 64, None, None).is_err());
+	}
+
+	#[test]
+	#[allow(clippy::reversed_empty_ranges)]
+	fn fill_ranges() {
+		assert_eq!(fill_range(8..16), Ok((8, Some(8))));
+		assert_eq!(fill_range(8..=15), Ok((8, Some(8))));
+		assert_eq!(fill_range(8..), Ok((8, None)));
+		assert!(fill_range(8..=3).is_err());
+		assert!(fill_range(8..3).is_err());
+		assert!(fill_range(..=usize::MAX).is_err());
+	}
+
+	#[test]
+	fn update_limit() {
+		let size = MAX_UPDATE_BUFFER_SIZE + 4;
+		assert!(validate_buffer_write(size, 0, Some(size), Some(MAX_UPDATE_BUFFER_SIZE)).is_err());
+	}
+}
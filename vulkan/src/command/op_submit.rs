@@ -1,64 +1,438 @@
-use crate::{backend, command, flags};
-use std::sync;
+use crate::{
+	backend, command, flags,
+	utility::{self, BoundObject, NamedObject},
+};
+use std::{
+	collections::{HashMap, HashSet},
+	sync,
+};
 
 /// Data used to submit commands to a [`Queue`](crate::device::logical::Queue).
 ///
+/// Every semaphore the submission waits on is paired with the stages which wait for it,
+/// and the resources used by each command buffer are kept alive by the submission.
+/// Multiple infos can be submitted as a batch in a single call to [`Queue::submit`](crate::device::logical::Queue::submit),
+/// which validates the whole batch before anything is submitted.
+///
 /// It is NOT safe to keep this struct around for more than 1 stack,
 /// as it stores unsafe Vulkan handles/pointers.
+#[derive(Default)]
 pub struct SubmitInfo {
-	semaphores_to_wait_for: Vec<backend::vk::Semaphore>,
-	stages_waited_for: Vec<backend::vk::PipelineStageFlags>,
-	buffers: Vec<backend::vk::CommandBuffer>,
-	semaphors_to_signal_when_complete: Vec<backend::vk::Semaphore>,
+	waits: Vec<Wait>,
+	buffers: Vec<SubmittedBuffer>,
+	signals: Vec<Signal>,
+	ownership_transfers: Vec<command::PendingHalf>,
+	timelines_signaled: Vec<(sync::Arc<command::TimelineSemaphore>, u64)>,
+	bound_objects: Vec<BoundObject>,
 }
 
-impl Default for SubmitInfo {
-	fn default() -> SubmitInfo {
-		SubmitInfo {
-			semaphores_to_wait_for: Vec::new(),
-			stages_waited_for: Vec::new(),
-			buffers: Vec::new(),
-			semaphors_to_signal_when_complete: Vec::new(),
-		}
-	}
+/// A semaphore which is waited on by a submission, and the stages that wait for it.
+struct Wait {
+	semaphore: backend::vk::Semaphore,
+	name: String,
+	stages: flags::StageSet,
+	/// The value a timeline must reach, or None for binary semaphores.
+	value: Option<u64>,
+}
+
+struct SubmittedBuffer {
+	internal: backend::vk::CommandBuffer,
+	name: String,
+	simultaneous_use: bool,
+}
+
+struct Signal {
+	semaphore: backend::vk::Semaphore,
+	name: String,
+	/// The value a timeline is set to, or None for binary semaphores.
+	value: Option<u64>,
 }
 
 impl SubmitInfo {
 	/// Adds a collection of signals on the GPU that the command buffer should wait
 	/// for before executing the commands in the buffer being presented.
-	pub fn wait_for_semaphores(mut self, semaphores: &Vec<sync::Arc<command::Semaphore>>) -> Self {
-		for rc in semaphores {
-			self.semaphores_to_wait_for.push(***rc);
-		}
-		self
+	/// Each semaphore is paired with the stages which wait for it.
+	pub fn wait_for_semaphores<'a, TStages>(
+		self,
+		semaphores: impl IntoIterator<Item = (&'a command::Semaphore, TStages)>,
+	) -> Self
+	where
+		TStages: Into<flags::StageSet>,
+	{
+		semaphores
+			.into_iter()
+			.fold(self, |info, (semaphore, stages)| {
+				info.wait_for(semaphore, stages)
+			})
 	}
 
 	/// Adds a signal on the GPU that the command buffer should wait
 	/// for before executing the commands in the buffer being presented.
-	pub fn wait_for(mut self, semaphore: &command::Semaphore, stage: flags::PipelineStage) -> Self {
-		self.semaphores_to_wait_for.push(**semaphore);
-		self.stages_waited_for.push(stage.into());
+	/// The stage can be a single [`PipelineStage`](flags::PipelineStage) or any [`StageSet`](flags::StageSet).
+	pub fn wait_for(
+		mut self,
+		semaphore: &command::Semaphore,
+		stage: impl Into<flags::StageSet>,
+	) -> Self {
+		self.waits.push(Wait {
+			semaphore: **semaphore,
+			name: semaphore.name().clone(),
+			stages: stage.into(),
+			value: None,
+		});
+		self
+	}
+
+	/// Adds a timeline on the GPU whose counter must reach at least `value`
+	/// before the `stage` of the commands in the buffer are executed.
+	pub fn wait_for_timeline(
+		mut self,
+		semaphore: &command::TimelineSemaphore,
+		value: u64,
+		stage: impl Into<flags::StageSet>,
+	) -> Self {
+		self.waits.push(Wait {
+			semaphore: **semaphore,
+			name: semaphore.name().clone(),
+			stages: stage.into(),
+			value: Some(value),
+		});
 		self
 	}
 
 	/// Adds a command buffer that should be submitted to the GPU for execution.
+	///
+	/// The resources bound while recording the buffer are shared with the [`Submission`](command::Submission),
+	/// which keeps them alive until the GPU has finished executing it.
+	/// The buffer keeps its own references, so it can be submitted again (or retried if the submit fails).
 	pub fn add_buffer(mut self, buffer: &command::Buffer) -> Self {
-		self.buffers.push(**buffer);
+		self.buffers.push(SubmittedBuffer {
+			internal: **buffer,
+			name: buffer.name().clone(),
+			simultaneous_use: buffer
+				.usage()
+				.contains(flags::CommandBufferUsage::SIMULTANEOUS_USE),
+		});
+		self.keep_alive(buffer.ownership_transfers(), buffer.bound_objects())
+	}
+
+	/// Adds multiple command buffers that should be submitted to the GPU for execution, in order.
+	pub fn add_buffers<'a>(self, buffers: impl IntoIterator<Item = &'a command::Buffer>) -> Self {
+		buffers.into_iter().fold(self, Self::add_buffer)
+	}
+
+	/// Shares the ownership transfers and resources of a command buffer with the info.
+	fn keep_alive(
+		mut self,
+		ownership_transfers: &[command::PendingHalf],
+		bound_objects: &[BoundObject],
+	) -> Self {
+		self.ownership_transfers
+			.extend(ownership_transfers.iter().cloned());
+		self.bound_objects.extend(bound_objects.iter().cloned());
 		self
 	}
 
 	/// Adds a GPU signal that should be marked as "signaled" when the command buffer has been executed.
 	pub fn signal_when_complete(mut self, semaphore: &command::Semaphore) -> Self {
-		self.semaphors_to_signal_when_complete.push(**semaphore);
+		self.signals.push(Signal {
+			semaphore: **semaphore,
+			name: semaphore.name().clone(),
+			value: None,
+		});
 		self
 	}
 
-	pub(crate) fn as_vk(&self) -> backend::vk::SubmitInfo {
-		backend::vk::SubmitInfo::builder()
-			.wait_semaphores(&self.semaphores_to_wait_for)
-			.wait_dst_stage_mask(&self.stages_waited_for[..])
-			.command_buffers(&self.buffers)
-			.signal_semaphores(&self.semaphors_to_signal_when_complete)
+	/// Adds a timeline on the GPU whose counter is set to `value` when the command buffer has been executed.
+	/// The value must be greater than the counter's value when the submission completes.
+	pub fn signal_timeline(
+		mut self,
+		semaphore: &sync::Arc<command::TimelineSemaphore>,
+		value: u64,
+	) -> Self {
+		self.signals.push(Signal {
+			semaphore: ***semaphore,
+			name: semaphore.name().clone(),
+			value: Some(value),
+		});
+		self.timelines_signaled.push((semaphore.clone(), value));
+		self
+	}
+
+	fn waited_semaphores(&self) -> Vec<backend::vk::Semaphore> {
+		self.waits.iter().map(|wait| wait.semaphore).collect()
+	}
+
+	fn signaled_semaphores(&self) -> Vec<backend::vk::Semaphore> {
+		self.signals.iter().map(|signal| signal.semaphore).collect()
+	}
+
+	/// Returns an error if the batch of infos cannot be submitted, in order, to a queue of `queue_family`.
+	///
+	/// Checks that every wait has stages, that binary semaphores alternate between being signaled and waited on,
+	/// that timeline signals increase, that command buffers are only submitted more than once
+	/// if they were begun with [`SIMULTANEOUS_USE`](flags::CommandBufferUsage::SIMULTANEOUS_USE),
+	/// and that any [`ownership transfers`](command::OwnershipTransfer) are submitted correctly.
+	pub(crate) fn validate_batch(infos: &[SubmitInfo], queue_family: usize) -> utility::Result<()> {
+		let error = |index: usize, msg: String| {
+			Err(utility::Error::InvalidSubmission(format!(
+				"info {} {}",
+				index, msg
+			)))
+		};
+		let mut pending_binary_signals = HashSet::new();
+		let mut waited_binaries = HashSet::new();
+		let mut timeline_values = HashMap::new();
+		let mut buffers = HashSet::new();
+		for (index, info) in infos.iter().enumerate() {
+			for wait in info.waits.iter() {
+				if wait.stages.is_empty() {
+					return error(
+						index,
+						format!("waits on semaphore {:?} with no stages", wait.name),
+					);
+				}
+				if wait.value.is_none() {
+					pending_binary_signals.remove(&wait.semaphore);
+					if !waited_binaries.insert(wait.semaphore) {
+						return error(
+							index,
+							format!(
+								"waits on semaphore {:?}, which was already waited on by the batch without being signaled again",
+								wait.name
+							),
+						);
+					}
+				}
+			}
+			for buffer in info.buffers.iter() {
+				if !buffers.insert(buffer.internal) && !buffer.simultaneous_use {
+					return error(
+						index,
+						format!(
+							"submits command buffer {:?} more than once, but it was not begun with SIMULTANEOUS_USE",
+							buffer.name
+						),
+					);
+				}
+			}
+			for signal in info.signals.iter() {
+				match signal.value {
+					None => {
+						if !pending_binary_signals.insert(signal.semaphore) {
+							return error(
+								index,
+								format!(
+									"signals semaphore {:?}, which was already signaled by the batch without being waited on",
+									signal.name
+								),
+							);
+						}
+						waited_binaries.remove(&signal.semaphore);
+					}
+					Some(value) => {
+						if let Some(previous) = timeline_values.insert(signal.semaphore, value) {
+							if value <= previous {
+								return error(
+									index,
+									format!(
+										"signals timeline {:?} with value {}, which is not greater than the value {} signaled earlier in the batch",
+										signal.name, value, previous
+									),
+								);
+							}
+						}
+					}
+				}
+			}
+			for (semaphore, value) in info.timelines_signaled.iter() {
+				let current = semaphore.value()?;
+				if *value <= current {
+					return error(
+						index,
+						format!(
+							"signals timeline {:?} with value {}, but its counter is already {}",
+							semaphore.name(),
+							value,
+							current
+						),
+					);
+				}
+			}
+			let waits = info.waited_semaphores();
+			for transfer in info.ownership_transfers.iter() {
+				transfer.validate(queue_family, &waits[..])?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Marks the recorded [`ownership transfers`](command::OwnershipTransfer) as submitted.
+	pub(crate) fn mark_ownership_submitted(&self) {
+		let signals = self.signaled_semaphores();
+		for transfer in self.ownership_transfers.iter() {
+			transfer.mark_submitted(&signals[..]);
+		}
+	}
+
+	/// Moves the resources and signaled timelines out of the info, to be held by a [`Submission`](command::Submission).
+	pub(crate) fn take_completion_state(
+		&mut self,
+	) -> (
+		Vec<(sync::Arc<command::TimelineSemaphore>, u64)>,
+		Vec<BoundObject>,
+	) {
+		(
+			std::mem::take(&mut self.timelines_signaled),
+			std::mem::take(&mut self.bound_objects),
+		)
+	}
+
+	/// Returns the arrays which make up a Vulkan 1.0 submission,
+	/// which must outlive the result of [`LegacySubmit::as_vk`].
+	pub(crate) fn as_legacy(&self) -> LegacySubmit {
+		LegacySubmit {
+			wait_semaphores: self.waited_semaphores(),
+			wait_stages: self
+				.waits
+				.iter()
+				.map(|wait| wait.stages.as_legacy())
+				.collect(),
+			wait_values: self
+				.waits
+				.iter()
+				.map(|wait| wait.value.unwrap_or(0))
+				.collect(),
+			buffers: self.buffers.iter().map(|buffer| buffer.internal).collect(),
+			signal_semaphores: self.signaled_semaphores(),
+			signal_values: self
+				.signals
+				.iter()
+				.map(|signal| signal.value.unwrap_or(0))
+				.collect(),
+			uses_timelines: self.waits.iter().any(|wait| wait.value.is_some())
+				|| self.signals.iter().any(|signal| signal.value.is_some()),
+		}
+	}
+
+	/// Returns the synchronization2 structures which make up the submission,
+	/// which must outlive the result of [`Submit2::as_vk`].
+	pub(crate) fn as_vk2(&self) -> Submit2 {
+		Submit2 {
+			waits: self
+				.waits
+				.iter()
+				.map(|wait| {
+					backend::vk::SemaphoreSubmitInfo::builder()
+						.semaphore(wait.semaphore)
+						.stage_mask(wait.stages.as_vk2())
+						.value(wait.value.unwrap_or(0))
+						.build()
+				})
+				.collect(),
+			buffers: self
+				.buffers
+				.iter()
+				.map(|buffer| {
+					backend::vk::CommandBufferSubmitInfo::builder()
+						.command_buffer(buffer.internal)
+						.build()
+				})
+				.collect(),
+			signals: self
+				.signals
+				.iter()
+				.map(|signal| {
+					backend::vk::SemaphoreSubmitInfo::builder()
+						.semaphore(signal.semaphore)
+						.stage_mask(backend::vk::PipelineStageFlags2::ALL_COMMANDS)
+						.value(signal.value.unwrap_or(0))
+						.build()
+				})
+				.collect(),
+		}
+	}
+}
+
+/// The Vulkan 1.0 arrays for a [`SubmitInfo`].
+pub(crate) struct LegacySubmit {
+	wait_semaphores: Vec<backend::vk::Semaphore>,
+	wait_stages: Vec<backend::vk::PipelineStageFlags>,
+	wait_values: Vec<u64>,
+	buffers: Vec<backend::vk::CommandBuffer>,
+	signal_semaphores: Vec<backend::vk::Semaphore>,
+	signal_values: Vec<u64>,
+	uses_timelines: bool,
+}
+
+impl LegacySubmit {
+	/// Returns the timeline values for the submission if any timelines are used,
+	/// which must outlive the result of [`as_vk`](LegacySubmit::as_vk).
+	pub(crate) fn timeline_values(&self) -> Option<backend::vk::TimelineSemaphoreSubmitInfo> {
+		match self.uses_timelines {
+			true => Some(
+				backend::vk::TimelineSemaphoreSubmitInfo::builder()
+					.wait_semaphore_values(&self.wait_values[..])
+					.signal_semaphore_values(&self.signal_values[..])
+					.build(),
+			),
+			false => None,
+		}
+	}
+
+	pub(crate) fn as_vk(
+		&self,
+		timeline_values: Option<&backend::vk::TimelineSemaphoreSubmitInfo>,
+	) -> backend::vk::SubmitInfo {
+		let mut info = backend::vk::SubmitInfo::builder()
+			.wait_semaphores(&self.wait_semaphores[..])
+			.wait_dst_stage_mask(&self.wait_stages[..])
+			.command_buffers(&self.buffers[..])
+			.signal_semaphores(&self.signal_semaphores[..])
+			.build();
+		if let Some(values) = timeline_values {
+			info.p_next = values as *const _ as _;
+		}
+		info
+	}
+}
+
+/// The synchronization2 structures for a [`SubmitInfo`].
+pub(crate) struct Submit2 {
+	waits: Vec<backend::vk::SemaphoreSubmitInfo>,
+	buffers: Vec<backend::vk::CommandBufferSubmitInfo>,
+	signals: Vec<backend::vk::SemaphoreSubmitInfo>,
+}
+
+impl Submit2 {
+	pub(crate) fn as_vk(&self) -> backend::vk::SubmitInfo2 {
+		backend::vk::SubmitInfo2::builder()
+			.wait_semaphore_infos(&self.waits[..])
+			.command_buffer_infos(&self.buffers[..])
+			.signal_semaphore_infos(&self.signals[..])
 			.build()
 	}
 }
+
+#[cfg(test)]
+mod keep_alive {
+	use super::*;
+
+	#[test]
+	fn failed_submits_leave_the_buffer_resources_alive() {
+		let resource: BoundObject = sync::Arc::new(0u32);
+		let recorded = [resource.clone()];
+		let mut info = SubmitInfo::default().keep_alive(&[], &recorded[..]);
+		info.waits.push(Wait {
+			semaphore: backend::vk::Semaphore::null(),
+			name: "Semaphore".to_owned(),
+			stages: flags::StageSet::empty(),
+			value: None,
+		});
+		let infos = [info];
+		assert!(SubmitInfo::validate_batch(&infos[..], 0).is_err());
+		drop(infos);
+		assert_eq!(recorded.len(), 1);
+		assert_eq!(sync::Arc::strong_count(&resource), 2);
+	}
+}
@@ -0,0 +1,276 @@
+use crate::{
+	backend, buffer,
+	command::barrier,
+	device::logical,
+	flags::{AccessSet, ImageLayout, PipelineStage, StageSet},
+	image,
+	structs::subresource,
+	utility::{self, BoundObject},
+};
+use std::sync;
+
+/// Moves ownership of [`exclusive`](crate::flags::SharingMode::EXCLUSIVE) resources from one queue family to another
+/// (i.e. from a transfer queue which uploaded data to the graphics queue which renders with it).
+///
+/// A transfer is made of two halves, which are matching barriers recorded into different command buffers.
+/// The release is recorded by [`release_ownership`](crate::command::Buffer::release_ownership)
+/// into a command buffer submitted to a queue of the source family,
+/// and the acquire is recorded by [`acquire_ownership`](crate::command::Buffer::acquire_ownership)
+/// into a command buffer submitted to a queue of the destination family.
+///
+/// When the halves are [`submitted`](logical::Queue::submit), the queues validate that
+/// the release is submitted first, and that the acquire waits on a semaphore signaled by the release.
+pub struct OwnershipTransfer {
+	progress: sync::Arc<Progress>,
+	release: (StageSet, AccessSet),
+	acquire: (StageSet, AccessSet),
+	resources: Vec<Resource>,
+}
+
+enum Resource {
+	Buffer(sync::Arc<buffer::Buffer>),
+	Image {
+		image: sync::Arc<image::Image>,
+		range: subresource::Range,
+		old_layout: ImageLayout,
+		new_layout: ImageLayout,
+	},
+}
+
+impl OwnershipTransfer {
+	pub fn new(name: &str, src: &logical::Queue, dst: &logical::Queue) -> Self {
+		Self {
+			progress: sync::Arc::new(Progress {
+				name: name.to_owned(),
+				src_family: src.index(),
+				dst_family: dst.index(),
+				state: sync::Mutex::new(State::default()),
+			}),
+			release: (StageSet::empty(), AccessSet::empty()),
+			acquire: (StageSet::empty(), AccessSet::empty()),
+			resources: Vec::new(),
+		}
+	}
+
+	/// Sets the stages and accesses on the source queue which must complete before the release.
+	pub fn with_release(
+		mut self,
+		stages: impl Into<StageSet>,
+		access: impl Into<AccessSet>,
+	) -> Self {
+		self.release = (stages.into(), access.into());
+		self
+	}
+
+	/// Sets the stages and accesses on the destination queue which wait for the acquire.
+	pub fn with_acquire(
+		mut self,
+		stages: impl Into<StageSet>,
+		access: impl Into<AccessSet>,
+	) -> Self {
+		self.acquire = (stages.into(), access.into());
+		self
+	}
+
+	/// Includes the entirety of a buffer in the transfer.
+	pub fn with_buffer(mut self, buffer: &sync::Arc<buffer::Buffer>) -> Self {
+		self.resources.push(Resource::Buffer(buffer.clone()));
+		self
+	}
+
+	/// Includes a range of an image in the transfer, which changes layout as a part of the transfer.
+	pub fn with_image(
+		mut self,
+		image: &sync::Arc<image::Image>,
+		range: subresource::Range,
+		old_layout: ImageLayout,
+		new_layout: ImageLayout,
+	) -> Self {
+		self.resources.push(Resource::Image {
+			image: image.clone(),
+			range,
+			old_layout,
+			new_layout,
+		});
+		self
+	}
+
+	pub fn name(&self) -> &str {
+		&self.progress.name
+	}
+
+	/// Returns true if both halves of the transfer have been submitted.
+	pub fn is_complete(&self) -> bool {
+		let state = self.progress.state.lock().unwrap();
+		state.released.is_some() && state.acquired
+	}
+
+	/// Creates the barrier for one half of the transfer.
+	/// Only the release makes writes available, and only the acquire makes them visible.
+	pub(crate) fn barrier(&self, half: Half) -> barrier::Pipeline {
+		let (src_stages, src_access, dst_stages, dst_access) = match half {
+			Half::Release => (
+				self.release.0,
+				self.release.1,
+				PipelineStage::BottomOfPipe.into(),
+				AccessSet::empty(),
+			),
+			Half::Acquire => (
+				PipelineStage::TopOfPipe.into(),
+				AccessSet::empty(),
+				self.acquire.0,
+				self.acquire.1,
+			),
+		};
+		let (src_family, dst_family) = (self.progress.src_family, self.progress.dst_family);
+		self.resources.iter().fold(
+			barrier::Pipeline::new(src_stages, dst_stages),
+			|pipeline, resource| match resource {
+				Resource::Buffer(buffer) => pipeline.with(barrier::Kind::Buffer(
+					barrier::Buffer::new(buffer)
+						.with_queue_transfer(src_family, dst_family)
+						.with_access(src_access, dst_access),
+				)),
+				Resource::Image {
+					image,
+					range,
+					old_layout,
+					new_layout,
+				} => pipeline.with(barrier::Kind::Image(
+					barrier::Image::default()
+						.with_image(sync::Arc::downgrade(image))
+						.with_layout(*old_layout, *new_layout)
+						.with_range(*range)
+						.with_queue_transfer(src_family, dst_family)
+						.with_access(src_access, dst_access),
+				)),
+			},
+		)
+	}
+
+	/// The resources which must be kept alive while the transfer is being executed.
+	pub(crate) fn bound_objects(&self) -> Vec<BoundObject> {
+		self.resources
+			.iter()
+			.map(|resource| -> BoundObject {
+				match resource {
+					Resource::Buffer(buffer) => buffer.clone(),
+					Resource::Image { image, .. } => image.clone(),
+				}
+			})
+			.collect()
+	}
+
+	pub(crate) fn half(&self, half: Half) -> PendingHalf {
+		PendingHalf {
+			half,
+			progress: self.progress.clone(),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Half {
+	Release,
+	Acquire,
+}
+
+struct Progress {
+	name: String,
+	src_family: usize,
+	dst_family: usize,
+	state: sync::Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+	/// The semaphores signaled by the submission which contained the release, once it has been submitted.
+	released: Option<Vec<backend::vk::Semaphore>>,
+	acquired: bool,
+}
+
+impl Drop for Progress {
+	fn drop(&mut self) {
+		let state = self.state.get_mut().unwrap();
+		if state.released.is_some() && !state.acquired {
+			log::warn!(
+				target: crate::LOG,
+				"Ownership transfer {:?} was released but never acquired",
+				self.name
+			);
+		}
+	}
+}
+
+/// One half of an [`OwnershipTransfer`] which has been recorded into a command buffer,
+/// and is validated when the command buffer is submitted.
+#[derive(Clone)]
+pub(crate) struct PendingHalf {
+	half: Half,
+	progress: sync::Arc<Progress>,
+}
+
+impl PendingHalf {
+	/// Returns an error if submitting this half to a queue of `family`,
+	/// in a submission which waits on the semaphores `waits`, would be invalid.
+	pub(crate) fn validate(
+		&self,
+		family: usize,
+		waits: &[backend::vk::Semaphore],
+	) -> utility::Result<()> {
+		let progress = &self.progress;
+		let state = progress.state.lock().unwrap();
+		let error = |msg: String| {
+			Err(utility::Error::InvalidOwnershipTransfer(format!(
+				"{:?} {}",
+				progress.name, msg
+			)))
+		};
+		match self.half {
+			Half::Release => {
+				if family != progress.src_family {
+					return error(format!(
+						"was released on queue family {}, but transfers from family {}",
+						family, progress.src_family
+					));
+				}
+				if state.released.is_some() {
+					return error("was released more than once".to_owned());
+				}
+			}
+			Half::Acquire => {
+				if family != progress.dst_family {
+					return error(format!(
+						"was acquired on queue family {}, but transfers to family {}",
+						family, progress.dst_family
+					));
+				}
+				if state.acquired {
+					return error("was acquired more than once".to_owned());
+				}
+				let signals = match &state.released {
+					Some(signals) => signals,
+					None => {
+						return error("was acquired before the release was submitted".to_owned())
+					}
+				};
+				if !waits.iter().any(|semaphore| signals.contains(semaphore)) {
+					return error(
+						"was acquired without waiting on a semaphore signaled by the release"
+							.to_owned(),
+					);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Marks this half as submitted, in a submission which signals the semaphores `signals`.
+	pub(crate) fn mark_submitted(&self, signals: &[backend::vk::Semaphore]) {
+		let mut state = self.progress.state.lock().unwrap();
+		match self.half {
+			Half::Release => state.released = Some(signals.to_vec()),
+			Half::Acquire => state.acquired = true,
+		}
+	}
+}
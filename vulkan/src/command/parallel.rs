@@ -0,0 +1,158 @@
+use crate::{
+	command::{self, Pool, PoolBuilder},
+	device::logical,
+	flags,
+	utility::{self, BuildFromDevice, NameableBuilder},
+};
+use std::{sync, thread};
+
+/// Records secondary [`command buffers`](command::Buffer) on multiple threads,
+/// and executes them in order on a primary buffer.
+///
+/// Each worker thread has its own [`Pool`], as pools cannot be used from multiple threads at once.
+/// The secondaries inherit the render pass, subpass, and framebuffer that the primary is recording,
+/// and the resources they bind are merged into the primary so they are kept alive by its submission.
+///
+/// The pools are reset at the start of each [`record`](ParallelRecorder::record),
+/// so the primary from the previous recording must have finished executing on the GPU.
+/// Use one recorder per frame in flight (like a [`PoolRing`](command::PoolRing)).
+pub struct ParallelRecorder {
+	name: String,
+	workers: Vec<Worker>,
+}
+
+struct Worker {
+	pool: Pool,
+	buffers: Vec<command::Buffer>,
+}
+
+impl ParallelRecorder {
+	/// Creates a recorder with `thread_count` workers, each with a pool for the queue family.
+	pub fn new(
+		device: &sync::Arc<logical::Device>,
+		name: &str,
+		queue_family_index: usize,
+		thread_count: usize,
+	) -> anyhow::Result<Self> {
+		assert!(
+			thread_count > 0,
+			"A parallel recorder requires at least one thread"
+		);
+		let workers = (0..thread_count)
+			.map(|i| -> anyhow::Result<Worker> {
+				let pool = PoolBuilder::default()
+					.with_name(format!("{}.Thread{}", name, i))
+					.with_queue_family_index(queue_family_index)
+					.with_flag(flags::CommandPoolCreate::TRANSIENT)
+					.build(device)?;
+				Ok(Worker {
+					pool,
+					buffers: Vec::new(),
+				})
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?;
+		Ok(Self {
+			name: name.to_owned(),
+			workers,
+		})
+	}
+
+	pub fn thread_count(&self) -> usize {
+		self.workers.len()
+	}
+
+	/// Records each task into its own secondary buffer, spreading the tasks across the worker threads,
+	/// then [`executes`](command::Buffer::execute) the secondaries on `primary` in the order of `tasks`.
+	///
+	/// Returns the result of each task, in order, or the first error encountered.
+	/// If any task fails, nothing is executed on the primary.
+	/// If there are no tasks, nothing is recorded at all.
+	#[profiling::function]
+	pub fn record<T, F>(
+		&mut self,
+		primary: &mut command::Buffer,
+		tasks: Vec<F>,
+	) -> utility::Result<Vec<T>>
+	where
+		F: FnOnce(&mut command::Buffer) -> utility::Result<T> + Send,
+		T: Send,
+	{
+		if tasks.is_empty() {
+			return Ok(Vec::new());
+		}
+		let thread_count = self.workers.len();
+		let mut assignments = (0..thread_count).map(|_| Vec::new()).collect::<Vec<_>>();
+		for (index, task) in tasks.into_iter().enumerate() {
+			assignments[index % thread_count].push((index, task));
+		}
+
+		let name = &self.name;
+		for (thread_index, (worker, assigned)) in
+			self.workers.iter_mut().zip(assignments.iter()).enumerate()
+		{
+			worker.pool.reset(false)?;
+			if worker.buffers.len() < assigned.len() {
+				let names = (worker.buffers.len()..assigned.len())
+					.map(|i| format!("{}.Thread{}.Secondary{}", name, thread_index, i))
+					.collect::<Vec<_>>();
+				let buffers = worker
+					.pool
+					.allocate_named_buffers(names, flags::CommandBufferLevel::SECONDARY)?;
+				worker.buffers.extend(buffers);
+			}
+		}
+
+		let inherit_from: &command::Buffer = primary;
+		let recorded = thread::scope(|scope| {
+			let handles = self
+				.workers
+				.iter_mut()
+				.zip(assignments)
+				.enumerate()
+				.map(|(thread_index, (worker, assigned))| {
+					scope.spawn(move || -> utility::Result<Vec<(usize, usize, usize, T)>> {
+						let mut outputs = Vec::with_capacity(assigned.len());
+						for ((index, task), (buffer_index, buffer)) in assigned
+							.into_iter()
+							.zip(worker.buffers.iter_mut().enumerate())
+						{
+							buffer.begin(
+								Some(flags::CommandBufferUsage::ONE_TIME_SUBMIT),
+								Some(inherit_from),
+							)?;
+							let output = task(buffer)?;
+							buffer.end()?;
+							outputs.push((index, thread_index, buffer_index, output));
+						}
+						Ok(outputs)
+					})
+				})
+				.collect::<Vec<_>>();
+			handles
+				.into_iter()
+				.map(|handle| match handle.join() {
+					Ok(outputs) => outputs,
+					Err(panic) => std::panic::resume_unwind(panic),
+				})
+				.collect::<Vec<_>>()
+		});
+
+		let mut outputs = Vec::new();
+		for worker_outputs in recorded {
+			outputs.extend(worker_outputs?);
+		}
+		outputs.sort_by_key(|(index, ..)| *index);
+		primary.execute(
+			outputs
+				.iter()
+				.map(|(_, thread_index, buffer_index, _)| {
+					&self.workers[*thread_index].buffers[*buffer_index]
+				})
+				.collect::<Vec<_>>(),
+		);
+		Ok(outputs
+			.into_iter()
+			.map(|(_, _, _, output)| output)
+			.collect())
+	}
+}
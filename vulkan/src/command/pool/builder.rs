@@ -24,8 +24,9 @@ impl Builder {
 		self
 	}
 
+	/// Adds a creation flag to the pool. Flags from multiple calls are combined.
 	pub fn with_flag(mut self, flag: flags::CommandPoolCreate) -> Self {
-		self.flags = Some(flag);
+		self.flags = Some(self.flags.unwrap_or_default() | flag);
 		self
 	}
 }
@@ -45,12 +46,13 @@ impl utility::BuildFromDevice for Builder {
 	/// Creates a command pool from a device, queue, and a flag indicating the kind of command pool it is.
 	fn build(self, device: &sync::Arc<logical::Device>) -> anyhow::Result<Self::Output> {
 		use utility::NameableBuilder;
+		let flags = self.flags.unwrap_or_default();
 		let info = backend::vk::CommandPoolCreateInfo::builder()
 			.queue_family_index(self.queue_family_index as u32)
-			.flags(self.flags.unwrap_or_default())
+			.flags(flags)
 			.build();
 		let internal = unsafe { device.create_command_pool(&info, None) }?;
-		let pool = Pool::from(device.clone(), self.name().clone(), internal);
+		let pool = Pool::from(device.clone(), self.name().clone(), internal, flags);
 		self.set_object_name(device, &pool);
 		Ok(pool)
 	}
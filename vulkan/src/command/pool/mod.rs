@@ -3,3 +3,6 @@ pub use pool::*;
 
 mod builder;
 pub use builder::Builder as PoolBuilder;
+
+mod ring;
+pub use ring::*;
@@ -18,6 +18,7 @@ pub struct Pool {
 	name: String,
 	internal: backend::vk::CommandPool,
 	device: sync::Arc<logical::Device>,
+	flags: flags::CommandPoolCreate,
 }
 
 impl Pool {
@@ -29,14 +30,21 @@ impl Pool {
 		device: sync::Arc<logical::Device>,
 		name: String,
 		internal: backend::vk::CommandPool,
+		flags: flags::CommandPoolCreate,
 	) -> Self {
 		Self {
 			name,
 			internal,
 			device,
+			flags,
 		}
 	}
 
+	/// The flags the pool was created with.
+	pub fn flags(&self) -> flags::CommandPoolCreate {
+		self.flags
+	}
+
 	/// Creates some amount of [`command buffers`](command::Buffer) at a given level.
 	pub fn allocate_buffers(
 		&self,
@@ -67,8 +75,13 @@ impl Pool {
 			.into_iter()
 			.zip(buffer_names.iter())
 			.map(|(vk_buffer, buffer_name)| {
-				let buffer =
-					command::Buffer::from(self.device.clone(), buffer_name.clone(), vk_buffer);
+				let buffer = command::Buffer::from(
+					self.device.clone(),
+					buffer_name.clone(),
+					vk_buffer,
+					self.flags
+						.contains(flags::CommandPoolCreate::RESET_COMMAND_BUFFER),
+				);
 				self.device
 					.set_object_name_logged(&buffer.create_name(buffer_name));
 				buffer
@@ -87,6 +100,32 @@ impl Pool {
 				.free_command_buffers(self.internal, &vk_buffers[..])
 		};
 	}
+
+	/// Returns all of the buffers allocated from the pool to their initial state,
+	/// so they can be [`begun`](command::Buffer::begin) again. None of the buffers may be pending execution.
+	/// If `release_resources` is true, the memory used by the buffers is returned to the system.
+	///
+	/// Resetting the whole pool is generally much faster than resetting each buffer individually.
+	///
+	/// Equivalent to [`vkResetCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkResetCommandPool.html).
+	pub fn reset(&self, release_resources: bool) -> utility::Result<()> {
+		let flags = match release_resources {
+			true => backend::vk::CommandPoolResetFlags::RELEASE_RESOURCES,
+			false => backend::vk::CommandPoolResetFlags::empty(),
+		};
+		Ok(unsafe { self.device.reset_command_pool(self.internal, flags) }?)
+	}
+
+	/// Returns unused memory from the pool to the system,
+	/// without affecting any of the buffers allocated from it.
+	///
+	/// Equivalent to [`vkTrimCommandPool`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkTrimCommandPool.html).
+	pub fn trim(&self) {
+		unsafe {
+			self.device
+				.trim_command_pool(self.internal, backend::vk::CommandPoolTrimFlags::empty())
+		};
+	}
 }
 
 impl std::ops::Deref for Pool {
@@ -113,3 +152,9 @@ impl utility::HandledObject for Pool {
 		self.internal.as_raw()
 	}
 }
+
+impl utility::NamedObject for Pool {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
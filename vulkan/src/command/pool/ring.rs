@@ -0,0 +1,88 @@
+use super::{Pool, PoolBuilder};
+use crate::{
+	command::Fence,
+	device::logical,
+	flags,
+	utility::{self, BuildFromDevice, NameableBuilder},
+};
+use std::sync;
+
+/// A ring of [`command pools`](Pool), one for each frame that can be in flight at once.
+///
+/// Command buffers for a frame are allocated from (or re-recorded in) the frame's pool,
+/// and the whole pool is reset when the frame comes back around, once the fence for its last submission has signaled.
+/// Resetting a pool as a whole is much faster than resetting its buffers individually,
+/// which makes this the recommended way of recording commands every frame.
+pub struct PoolRing {
+	frames: Vec<Frame>,
+	current: usize,
+}
+
+struct Frame {
+	pool: Pool,
+	fence: Option<sync::Arc<Fence>>,
+}
+
+impl PoolRing {
+	/// Creates `frame_count` pools for a queue family.
+	/// The pools are [`TRANSIENT`](flags::CommandPoolCreate::TRANSIENT), as their buffers are only recorded for a single frame.
+	pub fn new(
+		device: &sync::Arc<logical::Device>,
+		name: &str,
+		queue_family_index: usize,
+		frame_count: usize,
+	) -> anyhow::Result<Self> {
+		assert!(frame_count > 0, "A pool ring requires at least one frame");
+		let frames = (0..frame_count)
+			.map(|i| -> anyhow::Result<Frame> {
+				let pool = PoolBuilder::default()
+					.with_name(format!("{}.Frame{}", name, i))
+					.with_queue_family_index(queue_family_index)
+					.with_flag(flags::CommandPoolCreate::TRANSIENT)
+					.build(device)?;
+				Ok(Frame { pool, fence: None })
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?;
+		Ok(Self {
+			// The first call to `begin_frame` moves to the first pool.
+			current: frames.len() - 1,
+			frames,
+		})
+	}
+
+	/// The number of pools in the ring.
+	pub fn frame_count(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// The index of the frame whose pool is currently in use.
+	pub fn frame_index(&self) -> usize {
+		self.current
+	}
+
+	/// The pool for the current frame.
+	pub fn pool(&self) -> &Pool {
+		&self.frames[self.current].pool
+	}
+
+	/// Moves to the next pool in the ring, blocking until the fence it was last
+	/// [`submitted with`](PoolRing::submitted_with) has signaled, and then [`resetting`](Pool::reset) it.
+	///
+	/// Buffers previously allocated from the pool return to their initial state,
+	/// and can be [`begun`](crate::command::Buffer::begin) again.
+	pub fn begin_frame(&mut self) -> utility::Result<&Pool> {
+		self.current = (self.current + 1) % self.frames.len();
+		let frame = &mut self.frames[self.current];
+		if let Some(fence) = frame.fence.take() {
+			fence.wait(u64::MAX)?;
+		}
+		frame.pool.reset(false)?;
+		Ok(&frame.pool)
+	}
+
+	/// Records the fence which signals when the GPU has finished with the buffers of the current frame.
+	/// The pool is not reset until the fence has signaled.
+	pub fn submitted_with(&mut self, fence: &sync::Arc<Fence>) {
+		self.frames[self.current].fence = Some(fence.clone());
+	}
+}
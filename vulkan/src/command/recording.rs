@@ -0,0 +1,605 @@
+use crate::{
+	buffer,
+	command::{self, frame, Buffer},
+	descriptor, flags, image, pipeline, query, renderpass,
+	structs::subresource,
+	utility::{self, Viewport},
+};
+use std::{ops::Range, sync::Arc};
+
+/// Commands which can be recorded both inside and outside of a render pass,
+/// available on both [`Recording`] and [`InRenderPass`].
+pub trait Recorder {
+	/// Returns the command buffer being recorded, for commands which the typestate API does not cover.
+	/// Any preconditions of those commands are not checked.
+	fn unchecked(&mut self) -> &mut Buffer;
+
+	/// See [`Buffer::begin_label`].
+	fn begin_label<TStr>(&mut self, name: TStr, color: [f32; 4])
+	where
+		TStr: Into<String>,
+	{
+		self.unchecked().begin_label(name, color);
+	}
+
+	/// See [`Buffer::insert_label`].
+	fn insert_label<TStr>(&mut self, name: TStr, color: [f32; 4])
+	where
+		TStr: Into<String>,
+	{
+		self.unchecked().insert_label(name, color);
+	}
+
+	/// See [`Buffer::end_label`].
+	fn end_label(&mut self) {
+		self.unchecked().end_label();
+	}
+
+	/// See [`Buffer::label_scope`].
+	#[must_use = "the label is ended as soon as the scope is dropped"]
+	fn label_scope<TStr>(&mut self, name: TStr, color: [f32; 4]) -> command::LabelScope<'_, Self>
+	where
+		Self: Sized,
+		TStr: Into<String>,
+	{
+		self.unchecked().begin_label(name, color);
+		command::LabelScope::new(self, Self::unchecked)
+	}
+
+	/// See [`Buffer::execute`].
+	fn execute<'a>(&mut self, secondary_buffers: impl IntoIterator<Item = &'a Buffer>) {
+		self.unchecked().execute(secondary_buffers);
+	}
+
+	/// See [`Buffer::bind_pipeline`].
+	fn bind_pipeline(
+		&mut self,
+		pipeline: &Arc<pipeline::Pipeline>,
+		bind_point: flags::PipelineBindPoint,
+	) {
+		self.unchecked().bind_pipeline(pipeline, bind_point);
+	}
+
+	/// See [`Buffer::bind_descriptors`].
+	fn bind_descriptors(
+		&mut self,
+		bind_point: flags::PipelineBindPoint,
+		layout: &pipeline::layout::Layout,
+		first_set_index: usize,
+		sets: Vec<&descriptor::Set>,
+	) {
+		self.unchecked()
+			.bind_descriptors(bind_point, layout, first_set_index, sets);
+	}
+
+	/// See [`Buffer::push_constant`].
+	fn push_constant<T>(
+		&mut self,
+		layout: &pipeline::layout::Layout,
+		stages: impl Into<enumset::EnumSet<flags::ShaderKind>>,
+		offset: usize,
+		data: &T,
+	) where
+		T: Sized + bytemuck::Pod,
+	{
+		self.unchecked().push_constant(layout, stages, offset, data);
+	}
+
+	/// See [`Buffer::push_constants`].
+	fn push_constants<T>(&mut self, block: &pipeline::layout::PushConstants<'_, T>, data: &T)
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		self.unchecked().push_constants(block, data);
+	}
+
+	/// See [`Buffer::set_dynamic_viewport`].
+	fn set_dynamic_viewport(&mut self, first: usize, viewports: Vec<Viewport>) {
+		self.unchecked().set_dynamic_viewport(first, viewports);
+	}
+
+	/// See [`Buffer::set_dynamic_scissors`].
+	fn set_dynamic_scissors(&mut self, first: usize, scissors: Vec<utility::Scissor>) {
+		self.unchecked().set_dynamic_scissors(first, scissors);
+	}
+
+	/// See [`Buffer::set_dynamic_line_width`].
+	fn set_dynamic_line_width(&mut self, width: f32) {
+		self.unchecked().set_dynamic_line_width(width);
+	}
+
+	/// See [`Buffer::set_dynamic_depth_bias`].
+	fn set_dynamic_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) {
+		self.unchecked()
+			.set_dynamic_depth_bias(constant_factor, clamp, slope_factor);
+	}
+
+	/// See [`Buffer::set_dynamic_blend_constants`].
+	fn set_dynamic_blend_constants(&mut self, constants: [f32; 4]) {
+		self.unchecked().set_dynamic_blend_constants(constants);
+	}
+
+	/// See [`Buffer::set_dynamic_depth_bounds`].
+	fn set_dynamic_depth_bounds(&mut self, min: f32, max: f32) {
+		self.unchecked().set_dynamic_depth_bounds(min, max);
+	}
+
+	/// See [`Buffer::set_dynamic_stencil_compare_mask`].
+	fn set_dynamic_stencil_compare_mask(&mut self, faces: flags::StencilFace, mask: u32) {
+		self.unchecked()
+			.set_dynamic_stencil_compare_mask(faces, mask);
+	}
+
+	/// See [`Buffer::set_dynamic_stencil_write_mask`].
+	fn set_dynamic_stencil_write_mask(&mut self, faces: flags::StencilFace, mask: u32) {
+		self.unchecked().set_dynamic_stencil_write_mask(faces, mask);
+	}
+
+	/// See [`Buffer::set_dynamic_stencil_reference`].
+	fn set_dynamic_stencil_reference(&mut self, faces: flags::StencilFace, reference: u32) {
+		self.unchecked()
+			.set_dynamic_stencil_reference(faces, reference);
+	}
+
+	/// See [`Buffer::set_dynamic_cull_mode`].
+	fn set_dynamic_cull_mode(&mut self, cull_mode: flags::CullMode) -> utility::Result<()> {
+		self.unchecked().set_dynamic_cull_mode(cull_mode)
+	}
+
+	/// See [`Buffer::set_dynamic_front_face`].
+	fn set_dynamic_front_face(&mut self, front_face: flags::FrontFace) -> utility::Result<()> {
+		self.unchecked().set_dynamic_front_face(front_face)
+	}
+
+	/// See [`Buffer::set_dynamic_primitive_topology`].
+	fn set_dynamic_primitive_topology(
+		&mut self,
+		topology: flags::PrimitiveTopology,
+	) -> utility::Result<()> {
+		self.unchecked().set_dynamic_primitive_topology(topology)
+	}
+
+	/// See [`Buffer::set_dynamic_depth_test_enable`].
+	fn set_dynamic_depth_test_enable(&mut self, enabled: bool) -> utility::Result<()> {
+		self.unchecked().set_dynamic_depth_test_enable(enabled)
+	}
+
+	/// See [`Buffer::set_dynamic_depth_write_enable`].
+	fn set_dynamic_depth_write_enable(&mut self, enabled: bool) -> utility::Result<()> {
+		self.unchecked().set_dynamic_depth_write_enable(enabled)
+	}
+
+	/// See [`Buffer::set_dynamic_depth_compare_op`].
+	fn set_dynamic_depth_compare_op(&mut self, op: flags::CompareOp) -> utility::Result<()> {
+		self.unchecked().set_dynamic_depth_compare_op(op)
+	}
+
+	/// See [`Buffer::set_dynamic_depth_bounds_test_enable`].
+	fn set_dynamic_depth_bounds_test_enable(&mut self, enabled: bool) -> utility::Result<()> {
+		self.unchecked()
+			.set_dynamic_depth_bounds_test_enable(enabled)
+	}
+
+	/// See [`Buffer::set_dynamic_stencil_test_enable`].
+	fn set_dynamic_stencil_test_enable(&mut self, enabled: bool) -> utility::Result<()> {
+		self.unchecked().set_dynamic_stencil_test_enable(enabled)
+	}
+
+	/// See [`Buffer::set_dynamic_stencil_op`].
+	fn set_dynamic_stencil_op(
+		&mut self,
+		faces: flags::StencilFace,
+		fail: flags::StencilOp,
+		pass: flags::StencilOp,
+		depth_fail: flags::StencilOp,
+		compare: flags::CompareOp,
+	) -> utility::Result<()> {
+		self.unchecked()
+			.set_dynamic_stencil_op(faces, fail, pass, depth_fail, compare)
+	}
+
+	/// See [`Buffer::bind_vertex_buffers`].
+	fn bind_vertex_buffers(
+		&mut self,
+		binding_index: u32,
+		buffers: Vec<&Arc<buffer::Buffer>>,
+		offsets: Vec<u64>,
+	) {
+		self.unchecked()
+			.bind_vertex_buffers(binding_index, buffers, offsets);
+	}
+
+	/// See [`Buffer::bind_index_buffer`].
+	fn bind_index_buffer(&mut self, buffer: &Arc<buffer::Buffer>, offset: u64) {
+		self.unchecked().bind_index_buffer(buffer, offset);
+	}
+
+	/// See [`Buffer::begin_query`].
+	fn begin_query(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		index: u32,
+		precise: bool,
+	) -> utility::Result<()> {
+		self.unchecked().begin_query(pool, index, precise)
+	}
+
+	/// See [`Buffer::end_query`].
+	fn end_query(&mut self, pool: &Arc<query::Pool>, index: u32) -> utility::Result<()> {
+		self.unchecked().end_query(pool, index)
+	}
+
+	/// See [`Buffer::write_timestamp`].
+	fn write_timestamp(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		index: u32,
+		stage: flags::PipelineStage,
+	) -> utility::Result<()> {
+		self.unchecked().write_timestamp(pool, index, stage)
+	}
+}
+
+/// A command buffer which has been begun, and which is not inside of a render pass.
+///
+/// Created by [`Buffer::begin_recording`], and finished by [`end`](Recording::end).
+/// Commands which can only be recorded inside of a render pass (like draws) are only available on the
+/// [`InRenderPass`] guard returned by [`start_render_pass`](Recording::start_render_pass),
+/// so recording commands in an invalid order is a compile error.
+#[must_use = "call end() to finish recording"]
+pub struct Recording<'a> {
+	buffer: &'a mut Buffer,
+}
+
+impl Buffer {
+	/// Begins the buffer, returning a [`Recording`] which only allows commands that are valid in the current state.
+	///
+	/// See [`begin`](Buffer::begin) for the meaning of the arguments.
+	#[must_use = "call end() to finish recording"]
+	pub fn begin_recording(
+		&mut self,
+		usage: Option<flags::CommandBufferUsage>,
+		primary: Option<&Buffer>,
+	) -> utility::Result<Recording<'_>> {
+		self.begin(usage, primary)?;
+		Ok(Recording { buffer: self })
+	}
+}
+
+impl<'a> Recording<'a> {
+	/// Finalizes the commands in the buffer, consuming the recording.
+	///
+	/// See [`Buffer::end`].
+	pub fn end(self) -> utility::Result<()> {
+		self.buffer.end()
+	}
+
+	/// Starts a render pass, returning a guard which stops the render pass when it is dropped.
+	///
+	/// See [`Buffer::start_render_pass`].
+	pub fn start_render_pass(
+		&mut self,
+		frame_buffer: &Arc<frame::Buffer>,
+		render_pass: &renderpass::Pass,
+		info: renderpass::RecordInstruction,
+		uses_secondary_buffers: bool,
+	) -> InRenderPass<'_> {
+		self.buffer
+			.start_render_pass(frame_buffer, render_pass, info, uses_secondary_buffers);
+		InRenderPass {
+			buffer: self.buffer,
+		}
+	}
+
+	/// See [`Buffer::mark_pipeline_barrier`].
+	pub fn mark_pipeline_barrier(&mut self, barrier: command::barrier::Pipeline) {
+		self.buffer.mark_pipeline_barrier(barrier);
+	}
+
+	/// See [`Buffer::set_event`].
+	pub fn set_event(&mut self, event: &Arc<command::Event>, barrier: &command::barrier::Pipeline) {
+		self.buffer.set_event(event, barrier);
+	}
+
+	/// See [`Buffer::wait_events`].
+	pub fn wait_events(&mut self, events: Vec<(&Arc<command::Event>, command::barrier::Pipeline)>) {
+		self.buffer.wait_events(events);
+	}
+
+	/// See [`Buffer::reset_event`].
+	pub fn reset_event(&mut self, event: &Arc<command::Event>, stages: impl Into<flags::StageSet>) {
+		self.buffer.reset_event(event, stages);
+	}
+
+	/// See [`Buffer::transition`].
+	pub fn transition(
+		&mut self,
+		image: &Arc<image::Image>,
+		layout: flags::ImageLayout,
+		stages: impl Into<flags::StageSet>,
+		access: impl Into<flags::AccessSet>,
+	) -> utility::Result<()> {
+		self.buffer.transition(image, layout, stages, access)
+	}
+
+	/// See [`Buffer::transition_buffer`].
+	pub fn transition_buffer(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		stages: impl Into<flags::StageSet>,
+		access: impl Into<flags::AccessSet>,
+	) -> utility::Result<()> {
+		self.buffer.transition_buffer(buffer, stages, access)
+	}
+
+	/// See [`Buffer::transition_all`].
+	pub fn transition_all(
+		&mut self,
+		transitions: Vec<command::barrier::Transition>,
+	) -> utility::Result<()> {
+		self.buffer.transition_all(transitions)
+	}
+
+	/// See [`Buffer::release_ownership`].
+	pub fn release_ownership(&mut self, transfer: &command::OwnershipTransfer) {
+		self.buffer.release_ownership(transfer);
+	}
+
+	/// See [`Buffer::acquire_ownership`].
+	pub fn acquire_ownership(&mut self, transfer: &command::OwnershipTransfer) {
+		self.buffer.acquire_ownership(transfer);
+	}
+
+	/// See [`Buffer::copy_buffer_to_image`].
+	pub fn copy_buffer_to_image(
+		&mut self,
+		buffer: &buffer::Buffer,
+		image: &image::Image,
+		layout: flags::ImageLayout,
+		regions: Vec<command::CopyBufferToImage>,
+	) {
+		self.buffer
+			.copy_buffer_to_image(buffer, image, layout, regions);
+	}
+
+	/// See [`Buffer::copy_image_to_buffer`].
+	pub fn copy_image_to_buffer(
+		&mut self,
+		image: &image::Image,
+		layout: flags::ImageLayout,
+		buffer: &buffer::Buffer,
+		regions: Vec<command::CopyImageToBuffer>,
+	) -> utility::Result<()> {
+		self.buffer
+			.copy_image_to_buffer(image, layout, buffer, regions)
+	}
+
+	/// See [`Buffer::copy_buffer_to_buffer`].
+	pub fn copy_buffer_to_buffer(
+		&mut self,
+		src: &buffer::Buffer,
+		dst: &buffer::Buffer,
+		regions: Vec<command::CopyBufferRange>,
+	) {
+		self.buffer.copy_buffer_to_buffer(src, dst, regions);
+	}
+
+	/// See [`Buffer::fill_buffer`].
+	pub fn fill_buffer(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		range: impl std::ops::RangeBounds<usize>,
+		data: u32,
+	) -> utility::Result<()> {
+		self.buffer.fill_buffer(buffer, range, data)
+	}
+
+	/// See [`Buffer::update_buffer`].
+	pub fn update_buffer<T>(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		offset: usize,
+		data: &T,
+	) -> utility::Result<()>
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		self.buffer.update_buffer(buffer, offset, data)
+	}
+
+	/// See [`Buffer::copy_image_to_image`].
+	pub fn copy_image_to_image(
+		&mut self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	) -> utility::Result<()> {
+		self.buffer
+			.copy_image_to_image(src, src_layout, dst, dst_layout, regions)
+	}
+
+	/// See [`Buffer::blit_image`].
+	pub fn blit_image(
+		&mut self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::BlitImage>,
+		filter: flags::Filter,
+	) -> utility::Result<()> {
+		self.buffer
+			.blit_image(src, src_layout, dst, dst_layout, regions, filter)
+	}
+
+	/// See [`Buffer::resolve_image`].
+	pub fn resolve_image(
+		&mut self,
+		src: &image::Image,
+		src_layout: flags::ImageLayout,
+		dst: &image::Image,
+		dst_layout: flags::ImageLayout,
+		regions: Vec<command::CopyImage>,
+	) -> utility::Result<()> {
+		self.buffer
+			.resolve_image(src, src_layout, dst, dst_layout, regions)
+	}
+
+	/// See [`Buffer::dispatch`].
+	pub fn dispatch(&mut self, group_counts: [u32; 3]) {
+		self.buffer.dispatch(group_counts);
+	}
+
+	/// See [`Buffer::dispatch_indirect`].
+	pub fn dispatch_indirect(
+		&mut self,
+		buffer: &Arc<buffer::Buffer>,
+		offset: usize,
+	) -> utility::Result<()> {
+		self.buffer.dispatch_indirect(buffer, offset)
+	}
+
+	/// See [`Buffer::dispatch_base`].
+	pub fn dispatch_base(&mut self, base_group: [u32; 3], group_counts: [u32; 3]) {
+		self.buffer.dispatch_base(base_group, group_counts);
+	}
+
+	/// See [`Buffer::reset_queries`].
+	pub fn reset_queries(
+		&mut self,
+		pool: &Arc<query::Pool>,
+		queries: Range<u32>,
+	) -> utility::Result<()> {
+		self.buffer.reset_queries(pool, queries)
+	}
+
+	/// See [`Buffer::clear_image`].
+	pub fn clear_image(
+		&mut self,
+		image: &image::Image,
+		layout: flags::ImageLayout,
+		value: renderpass::ClearValue,
+		ranges: Vec<subresource::Range>,
+	) -> utility::Result<()> {
+		self.buffer.clear_image(image, layout, value, ranges)
+	}
+}
+
+impl<'a> Recorder for Recording<'a> {
+	fn unchecked(&mut self) -> &mut Buffer {
+		self.buffer
+	}
+}
+
+/// A guard for a render pass which is being recorded, created by [`Recording::start_render_pass`].
+/// Draw commands are only available while the guard is alive, and the render pass is stopped when the guard is dropped.
+pub struct InRenderPass<'a> {
+	buffer: &'a mut Buffer,
+}
+
+impl<'a> InRenderPass<'a> {
+	/// Stops the render pass. Equivalent to dropping the guard.
+	///
+	/// See [`Buffer::stop_render_pass`].
+	pub fn stop(self) {}
+
+	/// See [`Buffer::next_subpass`].
+	pub fn next_subpass(&mut self, uses_secondary_buffers: bool) {
+		self.buffer.next_subpass(uses_secondary_buffers);
+	}
+
+	/// See [`Buffer::clear_attachments`].
+	pub fn clear_attachments(
+		&mut self,
+		attachments: Vec<command::ClearAttachment>,
+		rects: Vec<command::ClearRect>,
+	) -> utility::Result<()> {
+		self.buffer.clear_attachments(attachments, rects)
+	}
+
+	/// See [`Buffer::draw`].
+	pub fn draw(
+		&mut self,
+		index_count: usize,
+		first_index: usize,
+		instance_count: usize,
+		first_instance: usize,
+		vertex_offset: usize,
+	) {
+		self.buffer.draw(
+			index_count,
+			first_index,
+			instance_count,
+			first_instance,
+			vertex_offset,
+		);
+	}
+
+	/// See [`Buffer::draw_vertices`].
+	pub fn draw_vertices(
+		&mut self,
+		vertex_count: usize,
+		first_vertex: usize,
+		instance_count: usize,
+		first_instance: usize,
+	) {
+		self.buffer
+			.draw_vertices(vertex_count, first_vertex, instance_count, first_instance);
+	}
+
+	/// See [`Buffer::draw_indirect`].
+	pub fn draw_indirect(
+		&mut self,
+		draws: command::IndirectDraws,
+		draw_count: usize,
+	) -> utility::Result<()> {
+		self.buffer.draw_indirect(draws, draw_count)
+	}
+
+	/// See [`Buffer::draw_indexed_indirect`].
+	pub fn draw_indexed_indirect(
+		&mut self,
+		draws: command::IndirectDraws,
+		draw_count: usize,
+	) -> utility::Result<()> {
+		self.buffer.draw_indexed_indirect(draws, draw_count)
+	}
+
+	/// See [`Buffer::draw_indirect_count`].
+	pub fn draw_indirect_count(
+		&mut self,
+		draws: command::IndirectDraws,
+		count_buffer: &Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	) -> utility::Result<()> {
+		self.buffer
+			.draw_indirect_count(draws, count_buffer, count_offset, max_draw_count)
+	}
+
+	/// See [`Buffer::draw_indexed_indirect_count`].
+	pub fn draw_indexed_indirect_count(
+		&mut self,
+		draws: command::IndirectDraws,
+		count_buffer: &Arc<buffer::Buffer>,
+		count_offset: usize,
+		max_draw_count: usize,
+	) -> utility::Result<()> {
+		self.buffer
+			.draw_indexed_indirect_count(draws, count_buffer, count_offset, max_draw_count)
+	}
+}
+
+impl<'a> Recorder for InRenderPass<'a> {
+	fn unchecked(&mut self) -> &mut Buffer {
+		self.buffer
+	}
+}
+
+impl<'a> Drop for InRenderPass<'a> {
+	fn drop(&mut self) {
+		self.buffer.stop_render_pass();
+	}
+}
@@ -0,0 +1,137 @@
+use crate::{
+	command::{Fence, TimelineSemaphore, WaitStatus},
+	utility::{self, BoundObject},
+};
+use std::sync;
+
+/// Work which has been [`submitted`](crate::device::logical::Queue::submit) to a queue,
+/// holding on to the resources used by its command buffers until the GPU has finished with them.
+///
+/// Completion is determined by the fence and timeline values the submission signals.
+/// A submission which signals neither cannot observe its own completion,
+/// and holds its resources until [`Queue::wait_idle`](crate::device::logical::Queue::wait_idle) is called.
+///
+/// The resources are released the first time the submission is observed to be complete,
+/// either by [`poll`](Submission::poll), [`wait`](Submission::wait), or by the queue on its next submit.
+/// If a signaled fence is [`reset`](Fence::reset) before then, the resources are held until the queue is idle.
+pub struct Submission {
+	name: String,
+	signals: Vec<Signal>,
+	bound_objects: sync::Mutex<Option<Vec<BoundObject>>>,
+}
+
+enum Signal {
+	Fence(sync::Arc<Fence>),
+	Timeline(sync::Arc<TimelineSemaphore>, u64),
+}
+
+impl Signal {
+	fn wait(&self, timeout: u64) -> utility::Result<WaitStatus> {
+		match self {
+			Self::Fence(fence) => fence.wait(timeout),
+			Self::Timeline(semaphore, value) => semaphore.wait(*value, timeout),
+		}
+	}
+}
+
+impl Submission {
+	pub(crate) fn new(
+		name: String,
+		fence: Option<&sync::Arc<Fence>>,
+		timelines: Vec<(sync::Arc<TimelineSemaphore>, u64)>,
+		bound_objects: Vec<BoundObject>,
+	) -> Self {
+		let signals = fence
+			.map(|fence| Signal::Fence(fence.clone()))
+			.into_iter()
+			.chain(
+				timelines
+					.into_iter()
+					.map(|(semaphore, value)| Signal::Timeline(semaphore, value)),
+			)
+			.collect();
+		Self {
+			name,
+			signals,
+			bound_objects: sync::Mutex::new(Some(bound_objects)),
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns true if the submission signals a fence or timeline,
+	/// and can therefore detect when it has completed.
+	pub fn can_observe_completion(&self) -> bool {
+		!self.signals.is_empty()
+	}
+
+	/// Returns true if the resources have been released.
+	pub fn is_released(&self) -> bool {
+		self.bound_objects.lock().unwrap().is_none()
+	}
+
+	/// Returns the resources which the submission is keeping alive.
+	/// Empty once the submission has completed.
+	pub fn bound_objects(&self) -> Vec<BoundObject> {
+		self.bound_objects
+			.lock()
+			.unwrap()
+			.as_ref()
+			.map_or_else(Vec::new, |objects| objects.clone())
+	}
+
+	/// Returns true if the submission is keeping `object` alive.
+	pub fn is_holding<T>(&self, object: &sync::Arc<T>) -> bool
+	where
+		T: 'static + Send + Sync,
+	{
+		let target = sync::Arc::as_ptr(object) as *const u8;
+		self.bound_objects
+			.lock()
+			.unwrap()
+			.iter()
+			.flatten()
+			.any(|bound| sync::Arc::as_ptr(bound) as *const u8 == target)
+	}
+
+	/// Checks if the GPU has finished the submission without blocking,
+	/// releasing the resources if it has.
+	pub fn poll(&self) -> utility::Result<bool> {
+		self.wait(0).map(|status| status == WaitStatus::Ready)
+	}
+
+	/// Blocks the thread until the GPU has finished the submission,
+	/// or until `timeout` nanoseconds have elapsed, releasing the resources if it has finished.
+	///
+	/// A submission which cannot [`observe its completion`](Submission::can_observe_completion)
+	/// always times out.
+	pub fn wait(&self, timeout: u64) -> utility::Result<WaitStatus> {
+		if self.is_released() {
+			return Ok(WaitStatus::Ready);
+		}
+		if self.signals.is_empty() {
+			return Ok(WaitStatus::TimedOut);
+		}
+		for signal in self.signals.iter() {
+			if signal.wait(timeout)? == WaitStatus::TimedOut {
+				return Ok(WaitStatus::TimedOut);
+			}
+		}
+		self.release();
+		Ok(WaitStatus::Ready)
+	}
+
+	/// Drops the resources. Must only be called once the GPU has finished with them.
+	pub(crate) fn release(&self) {
+		if let Some(objects) = self.bound_objects.lock().unwrap().take() {
+			log::trace!(
+				target: crate::LOG,
+				"Releasing {} objects held by submission {:?}",
+				objects.len(),
+				self.name
+			);
+		}
+	}
+}
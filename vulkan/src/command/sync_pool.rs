@@ -0,0 +1,78 @@
+use crate::{
+	command::{Fence, Semaphore},
+	device::logical,
+	flags, utility,
+};
+use std::sync;
+
+/// Recycles [`Fences`](Fence) and binary [`Semaphores`](Semaphore)
+/// so that per-submission syncing objects do not need to be created and destroyed every frame.
+///
+/// Objects taken from the pool are owned by the caller until they are recycled.
+/// An object must not be recycled while it is still in use by a pending submission
+/// (i.e. a fence must have been waited on, and a semaphore's signal must have been waited on by another submission).
+pub struct SyncPool {
+	device: sync::Arc<logical::Device>,
+	name: String,
+	fences: sync::Mutex<Vec<Fence>>,
+	semaphores: sync::Mutex<Vec<Semaphore>>,
+	created: sync::atomic::AtomicUsize,
+}
+
+impl SyncPool {
+	pub fn new(device: &sync::Arc<logical::Device>, name: &str) -> Self {
+		Self {
+			device: device.clone(),
+			name: name.to_owned(),
+			fences: sync::Mutex::new(Vec::new()),
+			semaphores: sync::Mutex::new(Vec::new()),
+			created: sync::atomic::AtomicUsize::new(0),
+		}
+	}
+
+	fn next_name(&self, kind: &str) -> String {
+		let index = self.created.fetch_add(1, sync::atomic::Ordering::Relaxed);
+		format!("{}.{}{}", self.name, kind, index)
+	}
+
+	/// Takes an unsignaled fence from the pool, creating one if none are available.
+	pub fn fence(&self) -> utility::Result<Fence> {
+		if let Some(fence) = self.fences.lock().unwrap().pop() {
+			return Ok(fence);
+		}
+		Fence::new(
+			&self.device,
+			&self.next_name("Fence"),
+			flags::FenceState::empty(),
+		)
+	}
+
+	/// Takes a binary semaphore from the pool, creating one if none are available.
+	pub fn semaphore(&self) -> utility::Result<Semaphore> {
+		if let Some(semaphore) = self.semaphores.lock().unwrap().pop() {
+			return Ok(semaphore);
+		}
+		Semaphore::new(&self.device, &self.next_name("Semaphore"))
+	}
+
+	/// Resets a fence and returns it to the pool.
+	pub fn recycle_fence(&self, fence: Fence) -> utility::Result<()> {
+		fence.reset()?;
+		self.fences.lock().unwrap().push(fence);
+		Ok(())
+	}
+
+	/// Returns a semaphore to the pool. The semaphore must be unsignaled
+	/// (its last signal must have been waited on, or it must never have been signaled).
+	pub fn recycle_semaphore(&self, semaphore: Semaphore) {
+		self.semaphores.lock().unwrap().push(semaphore);
+	}
+
+	/// The number of fences and semaphores which are available to be taken from the pool.
+	pub fn available(&self) -> (usize, usize) {
+		(
+			self.fences.lock().unwrap().len(),
+			self.semaphores.lock().unwrap().len(),
+		)
+	}
+}
@@ -19,7 +19,6 @@ pub enum SyncObject<'a> {
 pub struct Semaphore {
 	internal: backend::vk::Semaphore,
 	device: sync::Arc<logical::Device>,
-	#[allow(dead_code)]
 	name: String,
 }
 
@@ -76,6 +75,131 @@ impl HandledObject for Semaphore {
 	}
 }
 
+/// The result of waiting on a syncing object with a timeout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitStatus {
+	/// The object was signaled before the timeout.
+	Ready,
+	/// The timeout elapsed before the object was signaled.
+	TimedOut,
+}
+
+/// A semaphore whose state is a monotonically increasing 64-bit counter instead of a binary signal.
+///
+/// Submissions can wait for the counter to reach a value, and signal it to a new value when they complete
+/// (see [`SubmitInfo::wait_for_timeline`](crate::command::SubmitInfo::wait_for_timeline)
+/// and [`SubmitInfo::signal_timeline`](crate::command::SubmitInfo::signal_timeline)).
+/// Unlike binary semaphores, the CPU can also query, signal, and wait on the counter,
+/// so a single timeline can track the progress of a queue instead of a fence per submission.
+///
+/// Requires the device to be created with [`timeline semaphores`](logical::Info::with_timeline_semaphores).
+pub struct TimelineSemaphore {
+	internal: backend::vk::Semaphore,
+	device: sync::Arc<logical::Device>,
+	name: String,
+}
+
+impl TimelineSemaphore {
+	pub fn new(
+		device: &sync::Arc<logical::Device>,
+		name: &str,
+		initial_value: u64,
+	) -> utility::Result<TimelineSemaphore> {
+		if !device.is_timeline_semaphore_enabled() {
+			return Err(utility::Error::FeatureNotEnabled(
+				"timelineSemaphore".to_owned(),
+			));
+		}
+		let mut type_info = backend::vk::SemaphoreTypeCreateInfo::builder()
+			.semaphore_type(backend::vk::SemaphoreType::TIMELINE)
+			.initial_value(initial_value);
+		let info = backend::vk::SemaphoreCreateInfo::builder().push_next(&mut type_info);
+		let internal = unsafe { device.create_semaphore(&info, None) }?;
+		let semaphore = TimelineSemaphore {
+			device: device.clone(),
+			internal,
+			name: name.to_owned(),
+		};
+		device.set_object_name_logged(&semaphore.create_name(name));
+		Ok(semaphore)
+	}
+
+	/// Returns the current value of the counter.
+	///
+	/// Equivalent to [`vkGetSemaphoreCounterValue`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkGetSemaphoreCounterValue.html).
+	pub fn value(&self) -> utility::Result<u64> {
+		Ok(unsafe { self.device.get_semaphore_counter_value(self.internal) }?)
+	}
+
+	/// Sets the counter to a value from the CPU. The value must be greater than the current value.
+	///
+	/// Equivalent to [`vkSignalSemaphore`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkSignalSemaphore.html).
+	pub fn signal(&self, value: u64) -> utility::Result<()> {
+		let info = backend::vk::SemaphoreSignalInfo::builder()
+			.semaphore(self.internal)
+			.value(value);
+		Ok(unsafe { self.device.signal_semaphore(&info) }?)
+	}
+
+	/// Blocks the thread until the counter reaches at least `value`,
+	/// or until `timeout` nanoseconds have elapsed.
+	///
+	/// Equivalent to [`vkWaitSemaphores`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkWaitSemaphores.html).
+	pub fn wait(&self, value: u64, timeout: u64) -> utility::Result<WaitStatus> {
+		let semaphores = [self.internal];
+		let values = [value];
+		let info = backend::vk::SemaphoreWaitInfo::builder()
+			.semaphores(&semaphores)
+			.values(&values);
+		match unsafe { self.device.wait_semaphores(&info, timeout) } {
+			Ok(()) => Ok(WaitStatus::Ready),
+			Err(backend::vk::Result::TIMEOUT) => Ok(WaitStatus::TimedOut),
+			Err(err) => Err(err.into()),
+		}
+	}
+}
+
+impl std::ops::Deref for TimelineSemaphore {
+	type Target = backend::vk::Semaphore;
+	fn deref(&self) -> &Self::Target {
+		&self.internal
+	}
+}
+
+impl Drop for TimelineSemaphore {
+	fn drop(&mut self) {
+		log::debug!(
+			target: crate::LOG,
+			"Dropping TimelineSemaphore: {:?}",
+			self.name
+		);
+		unsafe { self.device.destroy_semaphore(self.internal, None) };
+	}
+}
+
+impl utility::NamedObject for Semaphore {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
+impl utility::NamedObject for TimelineSemaphore {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
+impl HandledObject for TimelineSemaphore {
+	fn kind(&self) -> backend::vk::ObjectType {
+		<backend::vk::Semaphore as backend::vk::Handle>::TYPE
+	}
+
+	fn handle(&self) -> u64 {
+		use backend::vk::Handle;
+		self.internal.as_raw()
+	}
+}
+
 /// A signal on the CPU that the GPU marks as signaled when a set of submitted commands have completed.
 ///
 /// Used for communicating from GPU to CPU.
@@ -110,6 +234,32 @@ impl Fence {
 			name,
 		}
 	}
+
+	/// Returns true if the fence is signaled, without blocking.
+	///
+	/// Equivalent to [`vkGetFenceStatus`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkGetFenceStatus.html).
+	pub fn status(&self) -> utility::Result<bool> {
+		Ok(unsafe { self.device.get_fence_status(self.internal) }?)
+	}
+
+	/// Blocks the thread until the fence is signaled, or until `timeout` nanoseconds have elapsed.
+	///
+	/// Equivalent to [`vkWaitForFences`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkWaitForFences.html).
+	pub fn wait(&self, timeout: u64) -> utility::Result<WaitStatus> {
+		match unsafe { self.device.wait_for_fences(&[self.internal], true, timeout) } {
+			Ok(()) => Ok(WaitStatus::Ready),
+			Err(backend::vk::Result::TIMEOUT) => Ok(WaitStatus::TimedOut),
+			Err(err) => Err(err.into()),
+		}
+	}
+
+	/// Returns the fence to the unsignaled state.
+	/// The fence must not be in use by a pending submission.
+	///
+	/// Equivalent to [`vkResetFences`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkResetFences.html).
+	pub fn reset(&self) -> utility::Result<()> {
+		self.device.reset_fences(&[self])
+	}
 }
 
 impl std::ops::Deref for Fence {
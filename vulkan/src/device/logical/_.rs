@@ -5,6 +5,7 @@ pub use device::Device;
 #[path = "info.rs"]
 mod info;
 pub use info::DeviceQueue;
+pub(crate) use info::EnabledFeatures;
 pub use info::Info;
 
 #[path = "queue.rs"]
@@ -11,9 +11,13 @@ use std::sync;
 /// which can send logical commands to the hardware.
 pub struct Device {
 	swapchain: backend::extensions::khr::Swapchain,
+	synchronization2: Option<backend::extensions::khr::Synchronization2>,
+	draw_indirect_count: Option<backend::extensions::khr::DrawIndirectCount>,
+	extended_dynamic_state: Option<backend::extensions::ext::ExtendedDynamicState>,
+	features: logical::EnabledFeatures,
 	internal: backend::Device,
 	// Hold strong reference to ensure the physical device is dropped after logical.
-	_physical: sync::Arc<physical::Device>,
+	physical: sync::Arc<physical::Device>,
 	instance: sync::Weak<Instance>,
 	name: String,
 }
@@ -25,16 +29,85 @@ impl Device {
 		physical_device: &sync::Arc<physical::Device>,
 		internal: backend::Device,
 		name: String,
+		features: logical::EnabledFeatures,
 	) -> Device {
 		Device {
 			instance: sync::Arc::downgrade(&instance),
-			_physical: physical_device.clone(),
+			physical: physical_device.clone(),
 			swapchain: backend::extensions::khr::Swapchain::new(&***instance, &internal),
+			synchronization2: match features.synchronization2 {
+				true => Some(backend::extensions::khr::Synchronization2::new(
+					&***instance,
+					&internal,
+				)),
+				false => None,
+			},
+			draw_indirect_count: match features.draw_indirect_count {
+				true => Some(backend::extensions::khr::DrawIndirectCount::new(
+					&***instance,
+					&internal,
+				)),
+				false => None,
+			},
+			extended_dynamic_state: match features.extended_dynamic_state {
+				true => Some(backend::extensions::ext::ExtendedDynamicState::new(
+					&***instance,
+					&internal,
+				)),
+				false => None,
+			},
+			features,
 			internal,
 			name,
 		}
 	}
 
+	/// The physical device (GPU) that the logical device was created from.
+	pub fn physical(&self) -> &sync::Arc<physical::Device> {
+		&self.physical
+	}
+
+	/// Returns true if the device was created with
+	/// [`synchronization2`](logical::Info::with_synchronization2) enabled,
+	/// in which case barriers and submissions use the synchronization2 commands.
+	pub fn is_synchronization2_enabled(&self) -> bool {
+		self.synchronization2.is_some()
+	}
+
+	/// Returns true if the device was created with
+	/// [`timeline semaphores`](logical::Info::with_timeline_semaphores) enabled.
+	pub fn is_timeline_semaphore_enabled(&self) -> bool {
+		self.features.timeline_semaphore
+	}
+
+	/// Returns true if the device was created with
+	/// [`draw indirect count`](logical::Info::with_draw_indirect_count) enabled.
+	pub fn is_draw_indirect_count_enabled(&self) -> bool {
+		self.draw_indirect_count.is_some()
+	}
+
+	pub(crate) fn draw_indirect_count(
+		&self,
+	) -> Option<&backend::extensions::khr::DrawIndirectCount> {
+		self.draw_indirect_count.as_ref()
+	}
+
+	/// Returns true if the device was created with
+	/// [`extended dynamic state`](logical::Info::with_extended_dynamic_state) enabled.
+	pub fn is_extended_dynamic_state_enabled(&self) -> bool {
+		self.extended_dynamic_state.is_some()
+	}
+
+	pub(crate) fn extended_dynamic_state(
+		&self,
+	) -> Option<&backend::extensions::ext::ExtendedDynamicState> {
+		self.extended_dynamic_state.as_ref()
+	}
+
+	pub(crate) fn synchronization2(&self) -> Option<&backend::extensions::khr::Synchronization2> {
+		self.synchronization2.as_ref()
+	}
+
 	pub fn create_queue(
 		device: &sync::Arc<Self>,
 		name: Option<String>,
@@ -6,6 +6,15 @@ use crate::{
 };
 use std::sync;
 
+/// The optional device features which were enabled when creating a [`logical::Device`].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct EnabledFeatures {
+	pub synchronization2: bool,
+	pub timeline_semaphore: bool,
+	pub draw_indirect_count: bool,
+	pub extended_dynamic_state: bool,
+}
+
 #[derive(Debug)]
 pub struct DeviceQueue {
 	pub queue_family_index: usize,
@@ -23,6 +32,7 @@ pub struct Info {
 
 	queues: Vec<DeviceQueue>,
 	features: backend::vk::PhysicalDeviceFeatures,
+	enabled_features: EnabledFeatures,
 
 	name: String,
 }
@@ -41,6 +51,7 @@ impl Default for Info {
 				.sampler_anisotropy(true)
 				.sample_rate_shading(true)
 				.build(),
+			enabled_features: EnabledFeatures::default(),
 
 			name: String::new(),
 		}
@@ -86,6 +97,54 @@ impl Info {
 		self
 	}
 
+	/// Enables the [`VK_KHR_synchronization2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VK_KHR_synchronization2.html)
+	/// extension and feature, so that barriers and submissions can use 64-bit stage & access flags.
+	/// Users should ensure that the extension is present in the constraints passed to
+	/// [`Instance.find_physical_device`](crate::instance::Instance::find_physical_device).
+	/// When not enabled, the legacy commands are used instead.
+	pub fn with_synchronization2(mut self) -> Self {
+		if !self.enabled_features.synchronization2 {
+			let name = backend::extensions::khr::Synchronization2::name();
+			self = self.add_extension(name.to_str().unwrap());
+			self.enabled_features.synchronization2 = true;
+		}
+		self
+	}
+
+	/// Enables the timeline semaphore feature (core in Vulkan 1.2),
+	/// which is required to create [`TimelineSemaphores`](crate::command::TimelineSemaphore).
+	pub fn with_timeline_semaphores(mut self) -> Self {
+		self.enabled_features.timeline_semaphore = true;
+		self
+	}
+
+	/// Enables the [`VK_KHR_draw_indirect_count`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VK_KHR_draw_indirect_count.html)
+	/// extension, which is required for [`draw_indirect_count`](crate::command::Buffer::draw_indirect_count).
+	/// Users should ensure that the extension is present in the constraints passed to
+	/// [`Instance.find_physical_device`](crate::instance::Instance::find_physical_device).
+	pub fn with_draw_indirect_count(mut self) -> Self {
+		if !self.enabled_features.draw_indirect_count {
+			let name = backend::extensions::khr::DrawIndirectCount::name();
+			self = self.add_extension(name.to_str().unwrap());
+			self.enabled_features.draw_indirect_count = true;
+		}
+		self
+	}
+
+	/// Enables the [`VK_EXT_extended_dynamic_state`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/VK_EXT_extended_dynamic_state.html)
+	/// extension and feature (core in Vulkan 1.3), which is required for dynamically setting states like
+	/// [`cull mode`](crate::command::Buffer::set_dynamic_cull_mode) and [`depth test`](crate::command::Buffer::set_dynamic_depth_test_enable).
+	/// Users should ensure that the extension is present in the constraints passed to
+	/// [`Instance.find_physical_device`](crate::instance::Instance::find_physical_device).
+	pub fn with_extended_dynamic_state(mut self) -> Self {
+		if !self.enabled_features.extended_dynamic_state {
+			let name = backend::extensions::ext::ExtendedDynamicState::name();
+			self = self.add_extension(name.to_str().unwrap());
+			self.enabled_features.extended_dynamic_state = true;
+		}
+		self
+	}
+
 	pub fn with_name<T>(mut self, name: T) -> Self
 	where
 		T: Into<String>,
@@ -135,9 +194,38 @@ impl Info {
 
 		info.p_enabled_features = &self.features as _;
 
+		let mut synchronization2 = backend::vk::PhysicalDeviceSynchronization2Features::builder()
+			.synchronization2(true)
+			.build();
+		if self.enabled_features.synchronization2 {
+			synchronization2.p_next = info.p_next as _;
+			info.p_next = &synchronization2 as *const _ as _;
+		}
+		let mut timeline_semaphore =
+			backend::vk::PhysicalDeviceTimelineSemaphoreFeatures::builder()
+				.timeline_semaphore(true)
+				.build();
+		if self.enabled_features.timeline_semaphore {
+			timeline_semaphore.p_next = info.p_next as _;
+			info.p_next = &timeline_semaphore as *const _ as _;
+		}
+		let mut extended_dynamic_state =
+			backend::vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::builder()
+				.extended_dynamic_state(true)
+				.build();
+		if self.enabled_features.extended_dynamic_state {
+			extended_dynamic_state.p_next = info.p_next as _;
+			info.p_next = &extended_dynamic_state as *const _ as _;
+		}
+
 		let internal = unsafe { instance.create_device(***physical_device, &info, None) }?;
-		let device =
-			logical::Device::from(&instance, &physical_device, internal, self.name.clone());
+		let device = logical::Device::from(
+			&instance,
+			&physical_device,
+			internal,
+			self.name.clone(),
+			self.enabled_features,
+		);
 		device.set_object_name_logged(&device.create_name(self.name.as_str()));
 		Ok(device)
 	}
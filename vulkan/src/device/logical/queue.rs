@@ -1,4 +1,4 @@
-use crate::{backend, command, device::logical, utility};
+use crate::{backend, command, device::logical, flags, utility};
 
 use std::sync;
 
@@ -6,6 +6,8 @@ pub struct Queue {
 	queue_family_index: usize,
 	internal: backend::vk::Queue,
 	device: sync::Arc<logical::Device>,
+	in_flight: sync::Mutex<Vec<sync::Arc<command::Submission>>>,
+	submission_count: sync::atomic::AtomicUsize,
 }
 
 impl Queue {
@@ -18,6 +20,8 @@ impl Queue {
 			device,
 			internal,
 			queue_family_index,
+			in_flight: sync::Mutex::new(Vec::new()),
+			submission_count: sync::atomic::AtomicUsize::new(0),
 		}
 	}
 
@@ -43,23 +47,164 @@ impl Queue {
 		self.device.end_queue_label(&self);
 	}
 
+	/// Begins a debug label on the queue, which is ended when the returned scope is dropped.
+	#[must_use = "the label is ended as soon as the scope is dropped"]
+	pub fn label_scope<TStr>(&self, name: TStr, color: [f32; 4]) -> QueueLabelScope<'_>
+	where
+		TStr: Into<String>,
+	{
+		self.begin_label(name, color);
+		QueueLabelScope { queue: self }
+	}
+
+	/// Submits command buffers to the queue, signaling the fence (if provided) when they have all completed.
+	///
+	/// The resources bound by the command buffers are held by the returned [`Submission`](command::Submission)
+	/// (and by the queue) until the fence or the timelines signaled by the infos show that the GPU is done with them.
+	/// Submissions which have completed are released whenever the queue submits more work,
+	/// or when [`release_completed`](Queue::release_completed) is called.
+	///
+	/// Equivalent to [`vkQueueSubmit2`](https://www.khronos.org/registry/vulkan/specs/1.3-extensions/man/html/vkQueueSubmit2.html)
+	/// if [`synchronization2`](logical::Info::with_synchronization2) is enabled,
+	/// otherwise [`vkQueueSubmit`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkQueueSubmit.html).
 	pub fn submit(
 		&self,
-		infos: Vec<command::SubmitInfo>,
-		signal_fence_when_complete: Option<&command::Fence>,
-	) -> utility::Result<()> {
-		let infos = infos
-			.iter()
-			.map(command::SubmitInfo::as_vk)
-			.collect::<Vec<_>>();
-		Ok(unsafe {
-			self.device.queue_submit(
-				self.internal,
-				&infos,
-				signal_fence_when_complete.map_or(backend::vk::Fence::null(), |obj| **obj),
-			)
-		}?)
+		mut infos: Vec<command::SubmitInfo>,
+		signal_fence_when_complete: Option<&sync::Arc<command::Fence>>,
+	) -> utility::Result<sync::Arc<command::Submission>> {
+		self.release_completed()?;
+		command::SubmitInfo::validate_batch(&infos[..], self.queue_family_index)?;
+		let fence = signal_fence_when_complete.map_or(backend::vk::Fence::null(), |obj| ***obj);
+		if let Some(synchronization2) = self.device.synchronization2() {
+			let submits = infos
+				.iter()
+				.map(command::SubmitInfo::as_vk2)
+				.collect::<Vec<_>>();
+			let vk_infos = submits
+				.iter()
+				.map(command::Submit2::as_vk)
+				.collect::<Vec<_>>();
+			unsafe { synchronization2.queue_submit2(self.internal, &vk_infos, fence) }?;
+		} else {
+			let submits = infos
+				.iter()
+				.map(command::SubmitInfo::as_legacy)
+				.collect::<Vec<_>>();
+			let timeline_values = submits
+				.iter()
+				.map(command::LegacySubmit::timeline_values)
+				.collect::<Vec<_>>();
+			let vk_infos = submits
+				.iter()
+				.zip(timeline_values.iter())
+				.map(|(submit, values)| submit.as_vk(values.as_ref()))
+				.collect::<Vec<_>>();
+			unsafe { self.device.queue_submit(self.internal, &vk_infos, fence) }?;
+		}
+		for info in infos.iter() {
+			info.mark_ownership_submitted();
+		}
+
+		let (mut timelines, mut bound_objects) = (Vec::new(), Vec::new());
+		for info in infos.iter_mut() {
+			let (info_timelines, info_objects) = info.take_completion_state();
+			timelines.extend(info_timelines);
+			bound_objects.extend(info_objects);
+		}
+		let index = self
+			.submission_count
+			.fetch_add(1, sync::atomic::Ordering::Relaxed);
+		let submission = sync::Arc::new(command::Submission::new(
+			format!("Queue{}.Submission{}", self.queue_family_index, index),
+			signal_fence_when_complete,
+			timelines,
+			bound_objects,
+		));
+		if !submission.can_observe_completion() {
+			log::debug!(
+				target: crate::LOG,
+				"{:?} signals no fence or timeline, its resources are held until the queue is idle",
+				submission.name()
+			);
+		}
+		self.in_flight.lock().unwrap().push(submission.clone());
+		Ok(submission)
+	}
+
+	/// Returns the submissions which are still holding on to resources.
+	pub fn in_flight(&self) -> Vec<sync::Arc<command::Submission>> {
+		self.in_flight.lock().unwrap().clone()
+	}
+
+	/// Releases the resources of any submissions which the GPU has finished, without blocking.
+	pub fn release_completed(&self) -> utility::Result<()> {
+		let mut in_flight = self.in_flight.lock().unwrap();
+		let mut still_pending = Vec::with_capacity(in_flight.len());
+		for submission in in_flight.drain(..) {
+			if !submission.poll()? {
+				still_pending.push(submission);
+			}
+		}
+		*in_flight = still_pending;
+		Ok(())
 	}
+
+	/// Blocks the thread until all work submitted to the queue has completed,
+	/// then releases the resources of every submission.
+	///
+	/// Equivalent to [`vkQueueWaitIdle`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/vkQueueWaitIdle.html).
+	pub fn wait_idle(&self) -> utility::Result<()> {
+		unsafe { self.device.queue_wait_idle(self.internal) }?;
+		for submission in self.in_flight.lock().unwrap().drain(..) {
+			submission.release();
+		}
+		Ok(())
+	}
+
+	/// Records and executes a single command buffer, blocking until the GPU has finished executing it.
+	/// Intended for small one-off tasks like uploads, layout transitions, and readbacks.
+	///
+	/// The buffer is allocated from `pool`, begun with [`ONE_TIME_SUBMIT`](flags::CommandBufferUsage::ONE_TIME_SUBMIT),
+	/// and passed to `record`. After the closure returns, the buffer is ended, submitted with a fence,
+	/// and waited on. Any resources bound while recording are kept alive until the GPU has finished,
+	/// after which the buffer is freed and the closure's result is returned.
+	///
+	/// If `record` returns an error, the buffer is freed without being submitted.
+	pub fn run_once<T, F>(&self, pool: &command::Pool, record: F) -> utility::Result<T>
+	where
+		F: FnOnce(&mut command::Buffer) -> utility::Result<T>,
+	{
+		use utility::NamedObject;
+		let mut buffers = pool.allocate_named_buffers(
+			vec![format!("{}.RunOnce", pool.name())],
+			flags::CommandBufferLevel::PRIMARY,
+		)?;
+		let result = self.run_once_recorded(&mut buffers[0], record);
+		pool.free_buffers(buffers);
+		result
+	}
+
+	fn run_once_recorded<T, F>(&self, buffer: &mut command::Buffer, record: F) -> utility::Result<T>
+	where
+		F: FnOnce(&mut command::Buffer) -> utility::Result<T>,
+	{
+		use utility::NamedObject;
+		buffer.begin(Some(flags::CommandBufferUsage::ONE_TIME_SUBMIT), None)?;
+		let output = record(buffer)?;
+		buffer.end()?;
+		let fence = sync::Arc::new(command::Fence::new(
+			&self.device,
+			&format!("{}.Fence", buffer.name()),
+			flags::FenceState::empty(),
+		)?);
+		let submission = self.submit(
+			vec![command::SubmitInfo::default().add_buffer(buffer)],
+			Some(&fence),
+		)?;
+		submission.wait(u64::MAX)?;
+		Ok(output)
+	}
+
 	/// returns true if the swapchain is suboptimal
 	#[profiling::function]
 	pub fn present(&self, info: command::PresentInfo) -> utility::Result</*suboptimal*/ bool> {
@@ -71,6 +216,19 @@ impl Queue {
 	}
 }
 
+/// A debug label on a [`Queue`], which is ended when the scope is dropped.
+/// Created by [`Queue::label_scope`].
+#[must_use]
+pub struct QueueLabelScope<'a> {
+	queue: &'a Queue,
+}
+
+impl<'a> Drop for QueueLabelScope<'a> {
+	fn drop(&mut self) {
+		self.queue.end_label();
+	}
+}
+
 impl std::ops::Deref for Queue {
 	type Target = backend::vk::Queue;
 	fn deref(&self) -> &Self::Target {
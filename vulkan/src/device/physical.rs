@@ -106,6 +106,25 @@ impl Device {
 		self.properties.limits.max_sampler_anisotropy
 	}
 
+	/// The maximum number of bytes which can be covered by push constant ranges.
+	pub fn max_push_constants_size(&self) -> u32 {
+		self.properties.limits.max_push_constants_size
+	}
+
+	/// The number of nanoseconds it takes for a timestamp query to be incremented by 1.
+	pub fn timestamp_period(&self) -> f32 {
+		self.properties.limits.timestamp_period
+	}
+
+	/// The number of meaningful bits in timestamps written on queues of a family,
+	/// or 0 if the family does not support timestamps.
+	pub fn timestamp_valid_bits(&self, queue_family_index: usize) -> u32 {
+		self.queue_families
+			.iter()
+			.find(|family| family.index == queue_family_index)
+			.map_or(0, |family| family.properties.timestamp_valid_bits)
+	}
+
 	/// Returns the descriptive name of the device (i.e. "GeForce RTX 2070").
 	pub fn name(&self) -> String {
 		unsafe { std::ffi::CStr::from_ptr(&self.properties.device_name as *const i8) }
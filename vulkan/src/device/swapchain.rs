@@ -73,11 +73,10 @@ pub trait Swapchain {
 			views.push(Arc::new(
 				View::builder()
 					.with_name(format!("{}.View", image.name()))
-					.for_image(image)
+					.for_image(image.clone())
 					.with_view_type(flags::ImageViewType::TYPE_2D)
 					.with_range(
-						structs::subresource::Range::default()
-							.with_aspect(flags::ImageAspect::COLOR),
+						structs::subresource::Range::default().with_format_aspects(image.format()),
 					)
 					.build(&self.device())?,
 			));
@@ -24,7 +24,7 @@ pub struct Builder {
 	image_color_space: ColorSpace,
 	pub(crate) image_extent: Extent2D,
 	image_array_layer_count: u32,
-	image_usage: ImageUsageFlags,
+	pub(crate) image_usage: ImageUsageFlags,
 	sharing_mode: SharingMode,
 	pre_transform: SurfaceTransform,
 	composite_alpha: CompositeAlpha,
@@ -101,6 +101,8 @@ impl Builder {
 	}
 
 	/// Mutates the build to specify the usage of the frame images.
+	/// Include [`TRANSFER_SRC`](ImageUsageFlags::TRANSFER_SRC) if the frame images will be
+	/// [`read back`](crate::image::Readback) to the CPU (i.e. for screenshots).
 	pub fn with_image_usage(mut self, usage: ImageUsageFlags) -> Self {
 		self.image_usage = usage;
 		self
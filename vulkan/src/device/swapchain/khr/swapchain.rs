@@ -17,6 +17,7 @@ use std::sync::{self, Arc};
 /// displayed images on the [`Surface`](crate::Surface).
 pub struct Swapchain {
 	image_format: flags::format::Format,
+	image_usage: flags::ImageUsageFlags,
 	image_extent: structs::Extent2D,
 	internal: backend::vk::SwapchainKHR,
 	device: sync::Arc<logical::Device>,
@@ -41,6 +42,7 @@ impl Swapchain {
 			device,
 			internal,
 			image_format: builder.image_format,
+			image_usage: builder.image_usage,
 			image_extent: builder.image_extent,
 			name: builder.name().clone(),
 			image_count: builder.image_count() as usize,
@@ -80,8 +82,13 @@ impl SwapchainTrait for Swapchain {
 		// no device reference is passed in because the images are a part of the swapchain
 		let images = images.map(|(i, image)| {
 			let name = format!("{}.Image", self.frame_name(i));
-			let image =
-				Image::from_swapchain(image, name.clone(), self.image_format, self.image_extent);
+			let image = Image::from_swapchain(
+				image,
+				name.clone(),
+				self.image_format,
+				self.image_usage,
+				self.image_extent,
+			);
 			self.device.set_object_name_logged(&image.create_name(name));
 			Arc::new(image)
 		});
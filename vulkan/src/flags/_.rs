@@ -1,5 +1,6 @@
 use crate::backend;
 
+pub use backend::vk::AccessFlags2 as Access2;
 pub use backend::vk::BorderColor;
 pub use backend::vk::BufferUsageFlags as BufferUsage;
 pub use backend::vk::ColorSpaceKHR as ColorSpace;
@@ -10,6 +11,7 @@ pub use backend::vk::CompareOp;
 pub use backend::vk::ComponentSwizzle;
 pub use backend::vk::CompositeAlphaFlagsKHR as CompositeAlpha;
 pub use backend::vk::CullModeFlags as CullMode;
+pub use backend::vk::DependencyFlags as Dependency;
 pub use backend::vk::DescriptorType as DescriptorKind;
 pub use backend::vk::DynamicState;
 pub use backend::vk::FenceCreateFlags as FenceState;
@@ -25,13 +27,16 @@ pub use backend::vk::ImageViewType;
 pub use backend::vk::IndexType;
 pub use backend::vk::MemoryPropertyFlags as MemoryProperty;
 pub use backend::vk::PipelineBindPoint;
+pub use backend::vk::PipelineStageFlags2 as PipelineStage2;
 pub use backend::vk::PolygonMode;
 pub use backend::vk::PresentModeKHR as PresentMode;
 pub use backend::vk::PrimitiveTopology;
+pub use backend::vk::QueryPipelineStatisticFlags as QueryPipelineStatistic;
 pub use backend::vk::QueueFlags;
 pub use backend::vk::SamplerAddressMode;
 pub use backend::vk::SamplerMipmapMode;
 pub use backend::vk::SharingMode;
+pub use backend::vk::StencilFaceFlags as StencilFace;
 pub use backend::vk::StencilOp;
 pub use backend::vk::SurfaceTransformFlagsKHR as SurfaceTransform;
 pub use backend::vk::VertexInputRate;
@@ -53,5 +58,7 @@ mod sample_count;
 pub use sample_count::*;
 mod shaderkind;
 pub use shaderkind::*;
+mod sync_set;
+pub use sync_set::*;
 mod attachment;
 pub use attachment::*;
@@ -1,5 +1,8 @@
 pub use crate::backend::vk::Format;
-use crate::flags::ColorComponent;
+use crate::{
+	flags::{ColorComponent, ImageAspect},
+	structs::Extent3D,
+};
 use serde::{Deserialize, Serialize};
 
 pub mod prelude {
@@ -19,6 +22,7 @@ pub enum DataType {
 	SignedInt,
 	SRGB,
 	SFloat,
+	UFloat,
 	Block,
 }
 
@@ -49,9 +53,68 @@ pub struct Components {
 }
 
 impl Components {
+	pub fn new(color: Vec<ColorComponent>, bits: Bits, data: DataType) -> Self {
+		Self { color, bits, data }
+	}
+
+	/// Returns the components of a format which can be created via [`format`](fn@format),
+	/// or `None` if the format is packed, depth/stencil, compressed, or undefined.
+	pub fn from_format(format: Format) -> Option<Self> {
+		find_components(format).map(|(color, bits, data)| Self::new(color.to_vec(), bits, data))
+	}
+
 	pub fn as_format(&self) -> Format {
 		format(&self.color, self.bits, self.data)
 	}
+
+	pub fn color(&self) -> &Vec<ColorComponent> {
+		&self.color
+	}
+
+	pub fn bits(&self) -> Bits {
+		self.bits
+	}
+
+	pub fn data(&self) -> DataType {
+		self.data
+	}
+}
+
+static COLOR_LAYOUTS: [&[ColorComponent]; 6] = [
+	&[R],
+	&[R, G],
+	&[R, G, B],
+	&[B, G, R],
+	&[R, G, B, A],
+	&[B, G, R, A],
+];
+static ALL_BITS: [Bits; 4] = [Bit8, Bit16, Bit32, Bit64];
+static ALL_DATA_TYPES: [DataType; 8] = [
+	UnsignedNorm,
+	SignedNorm,
+	UnsignedScaled,
+	SignedScaled,
+	UnsignedInt,
+	SignedInt,
+	SRGB,
+	SFloat,
+];
+
+/// The reverse of [`format`], searching all of the combinations it supports.
+fn find_components(target: Format) -> Option<(&'static [ColorComponent], Bits, DataType)> {
+	if target == Format::UNDEFINED {
+		return None;
+	}
+	for color in COLOR_LAYOUTS.iter() {
+		for bits in ALL_BITS.iter() {
+			for data in ALL_DATA_TYPES.iter() {
+				if format(color, *bits, *data) == target {
+					return Some((color, *bits, *data));
+				}
+			}
+		}
+	}
+	None
 }
 
 pub const fn format(comps: &[ColorComponent], bits: Bits, size: DataType) -> Format {
@@ -286,11 +349,325 @@ pub const fn format(comps: &[ColorComponent], bits: Bits, size: DataType) -> For
 }
 
 pub fn stencilable(format: Format) -> bool {
-	match format {
-		Format::S8_UINT => true,
-		Format::D16_UNORM_S8_UINT => true,
-		Format::D24_UNORM_S8_UINT => true,
-		Format::D32_SFLOAT_S8_UINT => true,
-		_ => false,
+	metadata(format).is_some_and(|info| info.has_stencil())
+}
+
+/// Information about the memory layout of a [`Format`], as provided by [`metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+	/// The number of bytes in a single block.
+	/// For uncompressed formats, a block is a single texel.
+	pub block_size: usize,
+	/// The number of texels in each dimension of a block.
+	/// Uncompressed formats always have a `1x1x1` block.
+	pub block_extent: Extent3D,
+	/// The color components of the format, in the order they are named by the format
+	/// (which is most-significant-bit first for packed formats).
+	/// Empty for depth/stencil formats.
+	pub components: &'static [ColorComponent],
+	/// How the values of the components are interpreted.
+	pub data: DataType,
+	/// The aspects of an image which has this format.
+	pub aspects: ImageAspect,
+	/// If all of the components are packed into a single 8, 16, or 32 bit value.
+	pub packed: bool,
+}
+
+impl Metadata {
+	/// Returns true if the format is one of the block-compressed formats (BC, ETC2/EAC, or ASTC).
+	pub fn is_compressed(&self) -> bool {
+		self.block_extent
+			!= Extent3D {
+				width: 1,
+				height: 1,
+				depth: 1,
+			}
+	}
+
+	/// Returns true if the color components are sRGB-encoded.
+	pub fn is_srgb(&self) -> bool {
+		self.data == SRGB
+	}
+
+	pub fn has_depth(&self) -> bool {
+		self.aspects.contains(ImageAspect::DEPTH)
+	}
+
+	pub fn has_stencil(&self) -> bool {
+		self.aspects.contains(ImageAspect::STENCIL)
+	}
+
+	/// Returns the number of bytes required to store an image of the provided size,
+	/// rounding up to whole blocks for compressed formats.
+	pub fn size_of(&self, extent: Extent3D) -> usize {
+		let blocks = |texels: u32, block: u32| texels.div_ceil(block) as usize;
+		blocks(extent.width, self.block_extent.width)
+			* blocks(extent.height, self.block_extent.height)
+			* blocks(extent.depth, self.block_extent.depth)
+			* self.block_size
+	}
+}
+
+const DEPTH_STENCIL: ImageAspect =
+	ImageAspect::from_raw(ImageAspect::DEPTH.as_raw() | ImageAspect::STENCIL.as_raw());
+
+const fn color(
+	block_size: usize,
+	components: &'static [ColorComponent],
+	data: DataType,
+) -> Metadata {
+	Metadata {
+		block_size,
+		block_extent: Extent3D {
+			width: 1,
+			height: 1,
+			depth: 1,
+		},
+		components,
+		data,
+		aspects: ImageAspect::COLOR,
+		packed: false,
+	}
+}
+
+const fn packed(
+	block_size: usize,
+	components: &'static [ColorComponent],
+	data: DataType,
+) -> Metadata {
+	Metadata {
+		packed: true,
+		..color(block_size, components, data)
+	}
+}
+
+const fn depth_stencil(block_size: usize, aspects: ImageAspect, data: DataType) -> Metadata {
+	Metadata {
+		aspects,
+		..color(block_size, &[], data)
+	}
+}
+
+const fn block(
+	block_size: usize,
+	(width, height): (u32, u32),
+	components: &'static [ColorComponent],
+	data: DataType,
+) -> Metadata {
+	Metadata {
+		block_extent: Extent3D {
+			width,
+			height,
+			depth: 1,
+		},
+		..color(block_size, components, data)
+	}
+}
+
+/// Returns the memory layout information for any core format,
+/// or `None` for [`undefined`](Format::UNDEFINED) and multi-planar/extension formats.
+pub fn metadata(format: Format) -> Option<Metadata> {
+	if let Some((components, bits, data)) = find_components(format) {
+		let bytes = match bits {
+			Bit8 => 1,
+			Bit16 => 2,
+			Bit32 => 4,
+			Bit64 => 8,
+		};
+		return Some(color(bytes * components.len(), components, data));
+	}
+	Some(match format {
+		Format::R4G4_UNORM_PACK8 => packed(1, &[R, G], UnsignedNorm),
+		Format::R4G4B4A4_UNORM_PACK16 => packed(2, &[R, G, B, A], UnsignedNorm),
+		Format::B4G4R4A4_UNORM_PACK16 => packed(2, &[B, G, R, A], UnsignedNorm),
+		Format::A4R4G4B4_UNORM_PACK16 => packed(2, &[A, R, G, B], UnsignedNorm),
+		Format::A4B4G4R4_UNORM_PACK16 => packed(2, &[A, B, G, R], UnsignedNorm),
+		Format::R5G6B5_UNORM_PACK16 => packed(2, &[R, G, B], UnsignedNorm),
+		Format::B5G6R5_UNORM_PACK16 => packed(2, &[B, G, R], UnsignedNorm),
+		Format::R5G5B5A1_UNORM_PACK16 => packed(2, &[R, G, B, A], UnsignedNorm),
+		Format::B5G5R5A1_UNORM_PACK16 => packed(2, &[B, G, R, A], UnsignedNorm),
+		Format::A1R5G5B5_UNORM_PACK16 => packed(2, &[A, R, G, B], UnsignedNorm),
+		Format::A8B8G8R8_UNORM_PACK32 => packed(4, &[A, B, G, R], UnsignedNorm),
+		Format::A8B8G8R8_SNORM_PACK32 => packed(4, &[A, B, G, R], SignedNorm),
+		Format::A8B8G8R8_USCALED_PACK32 => packed(4, &[A, B, G, R], UnsignedScaled),
+		Format::A8B8G8R8_SSCALED_PACK32 => packed(4, &[A, B, G, R], SignedScaled),
+		Format::A8B8G8R8_UINT_PACK32 => packed(4, &[A, B, G, R], UnsignedInt),
+		Format::A8B8G8R8_SINT_PACK32 => packed(4, &[A, B, G, R], SignedInt),
+		Format::A8B8G8R8_SRGB_PACK32 => packed(4, &[A, B, G, R], SRGB),
+		Format::A2R10G10B10_UNORM_PACK32 => packed(4, &[A, R, G, B], UnsignedNorm),
+		Format::A2R10G10B10_SNORM_PACK32 => packed(4, &[A, R, G, B], SignedNorm),
+		Format::A2R10G10B10_USCALED_PACK32 => packed(4, &[A, R, G, B], UnsignedScaled),
+		Format::A2R10G10B10_SSCALED_PACK32 => packed(4, &[A, R, G, B], SignedScaled),
+		Format::A2R10G10B10_UINT_PACK32 => packed(4, &[A, R, G, B], UnsignedInt),
+		Format::A2R10G10B10_SINT_PACK32 => packed(4, &[A, R, G, B], SignedInt),
+		Format::A2B10G10R10_UNORM_PACK32 => packed(4, &[A, B, G, R], UnsignedNorm),
+		Format::A2B10G10R10_SNORM_PACK32 => packed(4, &[A, B, G, R], SignedNorm),
+		Format::A2B10G10R10_USCALED_PACK32 => packed(4, &[A, B, G, R], UnsignedScaled),
+		Format::A2B10G10R10_SSCALED_PACK32 => packed(4, &[A, B, G, R], SignedScaled),
+		Format::A2B10G10R10_UINT_PACK32 => packed(4, &[A, B, G, R], UnsignedInt),
+		Format::A2B10G10R10_SINT_PACK32 => packed(4, &[A, B, G, R], SignedInt),
+		Format::B10G11R11_UFLOAT_PACK32 | Format::E5B9G9R9_UFLOAT_PACK32 => {
+			packed(4, &[B, G, R], UFloat)
+		}
+		Format::D16_UNORM => depth_stencil(2, ImageAspect::DEPTH, UnsignedNorm),
+		Format::X8_D24_UNORM_PACK32 => Metadata {
+			packed: true,
+			..depth_stencil(4, ImageAspect::DEPTH, UnsignedNorm)
+		},
+		Format::D32_SFLOAT => depth_stencil(4, ImageAspect::DEPTH, SFloat),
+		Format::S8_UINT => depth_stencil(1, ImageAspect::STENCIL, UnsignedInt),
+		Format::D16_UNORM_S8_UINT => depth_stencil(3, DEPTH_STENCIL, UnsignedNorm),
+		Format::D24_UNORM_S8_UINT => depth_stencil(4, DEPTH_STENCIL, UnsignedNorm),
+		Format::D32_SFLOAT_S8_UINT => depth_stencil(5, DEPTH_STENCIL, SFloat),
+		Format::BC1_RGB_UNORM_BLOCK => block(8, (4, 4), &[R, G, B], UnsignedNorm),
+		Format::BC1_RGB_SRGB_BLOCK => block(8, (4, 4), &[R, G, B], SRGB),
+		Format::BC1_RGBA_UNORM_BLOCK => block(8, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::BC1_RGBA_SRGB_BLOCK => block(8, (4, 4), &[R, G, B, A], SRGB),
+		Format::BC2_UNORM_BLOCK => block(16, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::BC2_SRGB_BLOCK => block(16, (4, 4), &[R, G, B, A], SRGB),
+		Format::BC3_UNORM_BLOCK => block(16, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::BC3_SRGB_BLOCK => block(16, (4, 4), &[R, G, B, A], SRGB),
+		Format::BC7_UNORM_BLOCK => block(16, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::BC7_SRGB_BLOCK => block(16, (4, 4), &[R, G, B, A], SRGB),
+		Format::BC4_UNORM_BLOCK => block(8, (4, 4), &[R], UnsignedNorm),
+		Format::BC4_SNORM_BLOCK => block(8, (4, 4), &[R], SignedNorm),
+		Format::BC5_UNORM_BLOCK => block(16, (4, 4), &[R, G], UnsignedNorm),
+		Format::BC5_SNORM_BLOCK => block(16, (4, 4), &[R, G], SignedNorm),
+		Format::BC6H_UFLOAT_BLOCK => block(16, (4, 4), &[R, G, B], UFloat),
+		Format::BC6H_SFLOAT_BLOCK => block(16, (4, 4), &[R, G, B], SFloat),
+		Format::ETC2_R8G8B8_UNORM_BLOCK => block(8, (4, 4), &[R, G, B], UnsignedNorm),
+		Format::ETC2_R8G8B8_SRGB_BLOCK => block(8, (4, 4), &[R, G, B], SRGB),
+		Format::ETC2_R8G8B8A1_UNORM_BLOCK => block(8, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::ETC2_R8G8B8A1_SRGB_BLOCK => block(8, (4, 4), &[R, G, B, A], SRGB),
+		Format::ETC2_R8G8B8A8_UNORM_BLOCK => block(16, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::ETC2_R8G8B8A8_SRGB_BLOCK => block(16, (4, 4), &[R, G, B, A], SRGB),
+		Format::EAC_R11_UNORM_BLOCK => block(8, (4, 4), &[R], UnsignedNorm),
+		Format::EAC_R11_SNORM_BLOCK => block(8, (4, 4), &[R], SignedNorm),
+		Format::EAC_R11G11_UNORM_BLOCK => block(16, (4, 4), &[R, G], UnsignedNorm),
+		Format::EAC_R11G11_SNORM_BLOCK => block(16, (4, 4), &[R, G], SignedNorm),
+		Format::ASTC_4X4_UNORM_BLOCK => block(16, (4, 4), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_4X4_SRGB_BLOCK => block(16, (4, 4), &[R, G, B, A], SRGB),
+		Format::ASTC_4X4_SFLOAT_BLOCK => block(16, (4, 4), &[R, G, B, A], SFloat),
+		Format::ASTC_5X4_UNORM_BLOCK => block(16, (5, 4), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_5X4_SRGB_BLOCK => block(16, (5, 4), &[R, G, B, A], SRGB),
+		Format::ASTC_5X4_SFLOAT_BLOCK => block(16, (5, 4), &[R, G, B, A], SFloat),
+		Format::ASTC_5X5_UNORM_BLOCK => block(16, (5, 5), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_5X5_SRGB_BLOCK => block(16, (5, 5), &[R, G, B, A], SRGB),
+		Format::ASTC_5X5_SFLOAT_BLOCK => block(16, (5, 5), &[R, G, B, A], SFloat),
+		Format::ASTC_6X5_UNORM_BLOCK => block(16, (6, 5), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_6X5_SRGB_BLOCK => block(16, (6, 5), &[R, G, B, A], SRGB),
+		Format::ASTC_6X5_SFLOAT_BLOCK => block(16, (6, 5), &[R, G, B, A], SFloat),
+		Format::ASTC_6X6_UNORM_BLOCK => block(16, (6, 6), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_6X6_SRGB_BLOCK => block(16, (6, 6), &[R, G, B, A], SRGB),
+		Format::ASTC_6X6_SFLOAT_BLOCK => block(16, (6, 6), &[R, G, B, A], SFloat),
+		Format::ASTC_8X5_UNORM_BLOCK => block(16, (8, 5), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_8X5_SRGB_BLOCK => block(16, (8, 5), &[R, G, B, A], SRGB),
+		Format::ASTC_8X5_SFLOAT_BLOCK => block(16, (8, 5), &[R, G, B, A], SFloat),
+		Format::ASTC_8X6_UNORM_BLOCK => block(16, (8, 6), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_8X6_SRGB_BLOCK => block(16, (8, 6), &[R, G, B, A], SRGB),
+		Format::ASTC_8X6_SFLOAT_BLOCK => block(16, (8, 6), &[R, G, B, A], SFloat),
+		Format::ASTC_8X8_UNORM_BLOCK => block(16, (8, 8), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_8X8_SRGB_BLOCK => block(16, (8, 8), &[R, G, B, A], SRGB),
+		Format::ASTC_8X8_SFLOAT_BLOCK => block(16, (8, 8), &[R, G, B, A], SFloat),
+		Format::ASTC_10X5_UNORM_BLOCK => block(16, (10, 5), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_10X5_SRGB_BLOCK => block(16, (10, 5), &[R, G, B, A], SRGB),
+		Format::ASTC_10X5_SFLOAT_BLOCK => block(16, (10, 5), &[R, G, B, A], SFloat),
+		Format::ASTC_10X6_UNORM_BLOCK => block(16, (10, 6), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_10X6_SRGB_BLOCK => block(16, (10, 6), &[R, G, B, A], SRGB),
+		Format::ASTC_10X6_SFLOAT_BLOCK => block(16, (10, 6), &[R, G, B, A], SFloat),
+		Format::ASTC_10X8_UNORM_BLOCK => block(16, (10, 8), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_10X8_SRGB_BLOCK => block(16, (10, 8), &[R, G, B, A], SRGB),
+		Format::ASTC_10X8_SFLOAT_BLOCK => block(16, (10, 8), &[R, G, B, A], SFloat),
+		Format::ASTC_10X10_UNORM_BLOCK => block(16, (10, 10), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_10X10_SRGB_BLOCK => block(16, (10, 10), &[R, G, B, A], SRGB),
+		Format::ASTC_10X10_SFLOAT_BLOCK => block(16, (10, 10), &[R, G, B, A], SFloat),
+		Format::ASTC_12X10_UNORM_BLOCK => block(16, (12, 10), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_12X10_SRGB_BLOCK => block(16, (12, 10), &[R, G, B, A], SRGB),
+		Format::ASTC_12X10_SFLOAT_BLOCK => block(16, (12, 10), &[R, G, B, A], SFloat),
+		Format::ASTC_12X12_UNORM_BLOCK => block(16, (12, 12), &[R, G, B, A], UnsignedNorm),
+		Format::ASTC_12X12_SRGB_BLOCK => block(16, (12, 12), &[R, G, B, A], SRGB),
+		Format::ASTC_12X12_SFLOAT_BLOCK => block(16, (12, 12), &[R, G, B, A], SFloat),
+		_ => return None,
+	})
+}
+
+#[cfg(test)]
+mod format_metadata {
+	use super::*;
+
+	#[test]
+	fn reverse_mapping() {
+		for color in COLOR_LAYOUTS.iter() {
+			for bits in ALL_BITS.iter() {
+				for data in ALL_DATA_TYPES.iter() {
+					let components = Components::new(color.to_vec(), *bits, *data);
+					let format = components.as_format();
+					if format != Format::UNDEFINED {
+						assert_eq!(Components::from_format(format), Some(components));
+					}
+				}
+			}
+		}
+		assert_eq!(Components::from_format(Format::UNDEFINED), None);
+		assert_eq!(Components::from_format(Format::D32_SFLOAT), None);
+	}
+
+	#[test]
+	fn uncompressed() {
+		let info = metadata(Format::B8G8R8A8_SRGB).unwrap();
+		assert_eq!(info.block_size, 4);
+		assert_eq!(info.components, &[B, G, R, A]);
+		assert_eq!(info.aspects, ImageAspect::COLOR);
+		assert!(info.is_srgb());
+		assert!(!info.is_compressed());
+		assert_eq!(
+			metadata(Format::R64G64B64A64_SFLOAT).unwrap().block_size,
+			32
+		);
+		assert_eq!(metadata(Format::R16G16B16_UNORM).unwrap().block_size, 6);
+	}
+
+	#[test]
+	fn packed_formats() {
+		let info = metadata(Format::A2B10G10R10_UNORM_PACK32).unwrap();
+		assert_eq!(info.block_size, 4);
+		assert!(info.packed);
+		assert_eq!(info.components, &[A, B, G, R]);
+		assert_eq!(metadata(Format::R5G6B5_UNORM_PACK16).unwrap().block_size, 2);
+		assert_eq!(
+			metadata(Format::B10G11R11_UFLOAT_PACK32).unwrap().data,
+			UFloat
+		);
+	}
+
+	#[test]
+	fn depth_stencil_formats() {
+		let info = metadata(Format::D24_UNORM_S8_UINT).unwrap();
+		assert!(info.has_depth() && info.has_stencil());
+		assert_eq!(info.aspects, ImageAspect::DEPTH | ImageAspect::STENCIL);
+		assert_eq!(metadata(Format::D32_SFLOAT_S8_UINT).unwrap().block_size, 5);
+		assert_eq!(
+			metadata(Format::D32_SFLOAT).unwrap().aspects,
+			ImageAspect::DEPTH
+		);
+		assert!(stencilable(Format::S8_UINT));
+		assert!(!stencilable(Format::D16_UNORM));
+	}
+
+	#[test]
+	fn compressed_sizes() {
+		let bc1 = metadata(Format::BC1_RGB_UNORM_BLOCK).unwrap();
+		assert!(bc1.is_compressed());
+		let extent = Extent3D {
+			width: 10,
+			height: 6,
+			depth: 1,
+		};
+		// 3x2 blocks of 8 bytes
+		assert_eq!(bc1.size_of(extent), 48);
+		let astc = metadata(Format::ASTC_12X10_SRGB_BLOCK).unwrap();
+		assert!(astc.is_srgb());
+		assert_eq!(astc.size_of(extent), 16);
+		assert_eq!(metadata(Format::UNDEFINED), None);
 	}
 }
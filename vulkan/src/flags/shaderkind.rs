@@ -45,6 +45,10 @@ impl ShaderKind {
 			ShaderKind::Callable => ShaderStageKind::CALLABLE_KHR,
 		}
 	}
+	pub fn fold(set: &enumset::EnumSet<Self>) -> ShaderStageKind {
+		set.iter()
+			.fold(ShaderStageKind::empty(), |vk, value| vk | value.to_vk())
+	}
 	pub fn to_shaderc(&self) -> shaderc::ShaderKind {
 		match self {
 			ShaderKind::Vertex => shaderc::ShaderKind::Vertex,
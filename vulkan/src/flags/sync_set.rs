@@ -0,0 +1,261 @@
+use crate::{
+	backend::vk,
+	flags::{Access, Access2, PipelineStage, PipelineStage2},
+};
+use enumset::EnumSet;
+
+/// A set of pipeline stages, stored as the 64-bit synchronization2 flags
+/// so that it can include stages which are not available in Vulkan 1.0
+/// (i.e. [`COPY`](PipelineStage2::COPY) or [`PRE_RASTERIZATION_SHADERS`](PipelineStage2::PRE_RASTERIZATION_SHADERS)).
+///
+/// Can be created from a single [`PipelineStage`], a set of them, or the raw [`PipelineStage2`] flags.
+/// When synchronization2 is not enabled on the device, the stages are converted to their
+/// nearest legacy equivalents (see [`as_legacy`](StageSet::as_legacy)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StageSet(PipelineStage2);
+
+impl StageSet {
+	pub fn empty() -> Self {
+		Self(PipelineStage2::NONE)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn contains(&self, other: impl Into<Self>) -> bool {
+		self.0.contains(other.into().0)
+	}
+
+	/// Returns the 64-bit synchronization2 flags.
+	pub fn as_vk2(&self) -> PipelineStage2 {
+		self.0
+	}
+
+	/// Returns the Vulkan 1.0 flags which cover all of the stages in the set.
+	/// Stages that were split up by synchronization2 are merged back into the legacy stage which contained them
+	/// (i.e. [`COPY`](PipelineStage2::COPY) becomes [`TRANSFER`](vk::PipelineStageFlags::TRANSFER)),
+	/// and any other stage which cannot be expressed becomes [`ALL_COMMANDS`](vk::PipelineStageFlags::ALL_COMMANDS).
+	pub fn as_legacy(&self) -> vk::PipelineStageFlags {
+		let mut stages2 = self.0;
+		let mut legacy = vk::PipelineStageFlags::empty();
+		let merged = [
+			(
+				PipelineStage2::COPY
+					| PipelineStage2::RESOLVE
+					| PipelineStage2::BLIT
+					| PipelineStage2::CLEAR,
+				vk::PipelineStageFlags::TRANSFER,
+			),
+			(
+				PipelineStage2::INDEX_INPUT | PipelineStage2::VERTEX_ATTRIBUTE_INPUT,
+				vk::PipelineStageFlags::VERTEX_INPUT,
+			),
+			(
+				PipelineStage2::PRE_RASTERIZATION_SHADERS,
+				vk::PipelineStageFlags::ALL_GRAPHICS,
+			),
+		];
+		for (split, combined) in merged.iter() {
+			if stages2.intersects(*split) {
+				legacy |= *combined;
+				stages2 &= !*split;
+			}
+		}
+		let lower = stages2.as_raw() & u32::MAX as u64;
+		legacy |= vk::PipelineStageFlags::from_raw(lower as u32);
+		if stages2.as_raw() != lower {
+			legacy |= vk::PipelineStageFlags::ALL_COMMANDS;
+		}
+		legacy
+	}
+}
+
+impl From<PipelineStage> for StageSet {
+	fn from(stage: PipelineStage) -> Self {
+		let legacy: vk::PipelineStageFlags = stage.into();
+		Self(PipelineStage2::from_raw(legacy.as_raw() as u64))
+	}
+}
+
+impl From<EnumSet<PipelineStage>> for StageSet {
+	fn from(set: EnumSet<PipelineStage>) -> Self {
+		set.iter()
+			.fold(Self::empty(), |stages, stage| stages | stage)
+	}
+}
+
+impl From<PipelineStage2> for StageSet {
+	fn from(flags: PipelineStage2) -> Self {
+		Self(flags)
+	}
+}
+
+impl<T: Into<StageSet>> std::ops::BitOr<T> for StageSet {
+	type Output = Self;
+	fn bitor(self, rhs: T) -> Self {
+		Self(self.0 | rhs.into().0)
+	}
+}
+
+impl<T: Into<StageSet>> std::ops::BitOrAssign<T> for StageSet {
+	fn bitor_assign(&mut self, rhs: T) {
+		self.0 |= rhs.into().0;
+	}
+}
+
+/// A set of memory accesses, stored as the 64-bit synchronization2 flags
+/// so that it can include accesses which are not available in Vulkan 1.0
+/// (i.e. [`SHADER_STORAGE_READ`](Access2::SHADER_STORAGE_READ)).
+///
+/// Can be created from a single [`Access`], a set of them, or the raw [`Access2`] flags.
+/// When synchronization2 is not enabled on the device, the accesses are converted to their
+/// nearest legacy equivalents (see [`as_legacy`](AccessSet::as_legacy)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AccessSet(Access2);
+
+impl AccessSet {
+	pub fn empty() -> Self {
+		Self(Access2::NONE)
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn contains(&self, other: impl Into<Self>) -> bool {
+		self.0.contains(other.into().0)
+	}
+
+	/// Returns the accesses in the set which write to memory.
+	pub fn writes(&self) -> Self {
+		Self(
+			self.0
+				& (Access2::SHADER_WRITE
+					| Access2::COLOR_ATTACHMENT_WRITE
+					| Access2::DEPTH_STENCIL_ATTACHMENT_WRITE
+					| Access2::TRANSFER_WRITE
+					| Access2::HOST_WRITE
+					| Access2::MEMORY_WRITE
+					| Access2::SHADER_STORAGE_WRITE
+					| Access2::TRANSFORM_FEEDBACK_WRITE_EXT
+					| Access2::ACCELERATION_STRUCTURE_WRITE_KHR
+					| Access2::COMMAND_PREPROCESS_WRITE_NV
+					| Access2::VIDEO_DECODE_WRITE_KHR),
+		)
+	}
+
+	/// Returns the 64-bit synchronization2 flags.
+	pub fn as_vk2(&self) -> Access2 {
+		self.0
+	}
+
+	/// Returns the Vulkan 1.0 flags which cover all of the accesses in the set.
+	/// Accesses that were split up by synchronization2 are merged back into the legacy access which contained them
+	/// (i.e. [`SHADER_STORAGE_READ`](Access2::SHADER_STORAGE_READ) becomes [`SHADER_READ`](vk::AccessFlags::SHADER_READ)),
+	/// and any other access which cannot be expressed becomes a generic memory read or write.
+	pub fn as_legacy(&self) -> vk::AccessFlags {
+		let mut access2 = self.0;
+		let mut legacy = vk::AccessFlags::empty();
+		let merged = [
+			(
+				Access2::SHADER_SAMPLED_READ | Access2::SHADER_STORAGE_READ,
+				vk::AccessFlags::SHADER_READ,
+			),
+			(Access2::SHADER_STORAGE_WRITE, vk::AccessFlags::SHADER_WRITE),
+		];
+		for (split, combined) in merged.iter() {
+			if access2.intersects(*split) {
+				legacy |= *combined;
+				access2 &= !*split;
+			}
+		}
+		let lower = access2.as_raw() & u32::MAX as u64;
+		legacy |= vk::AccessFlags::from_raw(lower as u32);
+		if access2.as_raw() != lower {
+			let remaining = Self(Access2::from_raw(access2.as_raw() & !lower));
+			legacy |= match remaining.writes().is_empty() {
+				true => vk::AccessFlags::MEMORY_READ,
+				false => vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE,
+			};
+		}
+		legacy
+	}
+}
+
+impl From<Access> for AccessSet {
+	fn from(access: Access) -> Self {
+		let legacy: vk::AccessFlags = access.into();
+		Self(Access2::from_raw(legacy.as_raw() as u64))
+	}
+}
+
+impl From<EnumSet<Access>> for AccessSet {
+	fn from(set: EnumSet<Access>) -> Self {
+		set.iter()
+			.fold(Self::empty(), |access, value| access | value)
+	}
+}
+
+impl From<Access2> for AccessSet {
+	fn from(flags: Access2) -> Self {
+		Self(flags)
+	}
+}
+
+impl<T: Into<AccessSet>> std::ops::BitOr<T> for AccessSet {
+	type Output = Self;
+	fn bitor(self, rhs: T) -> Self {
+		Self(self.0 | rhs.into().0)
+	}
+}
+
+impl<T: Into<AccessSet>> std::ops::BitOrAssign<T> for AccessSet {
+	fn bitor_assign(&mut self, rhs: T) {
+		self.0 |= rhs.into().0;
+	}
+}
+
+#[cfg(test)]
+mod legacy_conversion {
+	use super::*;
+
+	#[test]
+	fn legacy_stages_round_trip() {
+		let stages = StageSet::from(PipelineStage::Transfer | PipelineStage::FragmentShader);
+		assert_eq!(
+			stages.as_legacy(),
+			vk::PipelineStageFlags::TRANSFER | vk::PipelineStageFlags::FRAGMENT_SHADER
+		);
+		assert_eq!(
+			stages.as_vk2(),
+			PipelineStage2::TRANSFER | PipelineStage2::FRAGMENT_SHADER
+		);
+	}
+
+	#[test]
+	fn split_stages_merge() {
+		let stages = StageSet::from(PipelineStage2::COPY | PipelineStage2::INDEX_INPUT);
+		assert_eq!(
+			stages.as_legacy(),
+			vk::PipelineStageFlags::TRANSFER | vk::PipelineStageFlags::VERTEX_INPUT
+		);
+		let stages = StageSet::from(PipelineStage2::SUBPASS_SHADING_HUAWEI);
+		assert_eq!(stages.as_legacy(), vk::PipelineStageFlags::ALL_COMMANDS);
+	}
+
+	#[test]
+	fn split_accesses_merge() {
+		let access = AccessSet::from(Access2::SHADER_STORAGE_READ) | Access::TransferWrite;
+		assert_eq!(
+			access.as_legacy(),
+			vk::AccessFlags::SHADER_READ | vk::AccessFlags::TRANSFER_WRITE
+		);
+		let access = AccessSet::from(Access2::VIDEO_DECODE_WRITE_KHR);
+		assert!(!access.writes().is_empty());
+		assert_eq!(
+			access.as_legacy(),
+			vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE
+		);
+	}
+}
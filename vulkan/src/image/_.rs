@@ -3,3 +3,9 @@ pub use builder::*;
 
 mod image;
 pub use image::*;
+
+mod readback;
+pub use readback::*;
+
+mod state;
+pub use state::*;
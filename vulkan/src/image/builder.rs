@@ -25,6 +25,7 @@ pub struct Builder {
 	usage: ImageUsage,
 	sharing_mode: SharingMode,
 	initial_layout: ImageLayout,
+	track_state: bool,
 	name: String,
 }
 
@@ -42,6 +43,7 @@ impl Default for Builder {
 			usage: ImageUsage::default(),
 			sharing_mode: SharingMode::EXCLUSIVE,
 			initial_layout: ImageLayout::default(),
+			track_state: false,
 			name: String::new(),
 		}
 	}
@@ -90,10 +92,59 @@ impl Builder {
 		self
 	}
 
+	/// Returns the usage flags of the image to be allocated.
+	pub(crate) fn usage(&self) -> ImageUsage {
+		self.usage
+	}
+
 	pub fn with_sample_count(mut self, count: SampleCount) -> Self {
 		self.samples = count;
 		self
 	}
+
+	/// Mutates the builder to include a number of mip levels in the image.
+	/// Images have a single mip level by default.
+	pub fn with_mip_levels(mut self, count: u32) -> Self {
+		self.mip_levels = count;
+		self
+	}
+
+	/// Returns the number of mip levels in the image to be allocated.
+	pub(crate) fn mip_levels(&self) -> u32 {
+		self.mip_levels
+	}
+
+	/// Mutates the builder to include a number of array layers in the image.
+	/// Images have a single array layer by default.
+	pub fn with_array_layers(mut self, count: u32) -> Self {
+		self.array_layers = count;
+		self
+	}
+
+	/// Returns the number of array layers in the image to be allocated.
+	pub(crate) fn array_layers(&self) -> u32 {
+		self.array_layers
+	}
+
+	/// Mutates the builder so that the image tracks the layout and access of each of its subresources,
+	/// allowing [`transitions`](crate::command::Buffer::transition) to compute the barriers they require.
+	pub fn with_state_tracking(mut self) -> Self {
+		self.track_state = true;
+		self
+	}
+
+	/// Creates the tracker for the state of each subresource, starting in the initial layout,
+	/// if the image should track its state.
+	pub(crate) fn state_tracker(&self) -> Option<super::StateTracker> {
+		match self.track_state {
+			true => Some(super::StateTracker::new(
+				self.mip_levels,
+				self.array_layers,
+				self.initial_layout,
+			)),
+			false => None,
+		}
+	}
 }
 
 impl utility::NameableBuilder for Builder {
@@ -1,8 +1,8 @@
 use crate::{
 	alloc, backend,
 	flags::{format::Format, ImageUsage, MemoryLocation},
-	image::Builder,
-	structs::{Extent2D, Extent3D},
+	image::{Builder, StateChange, StateTracker, SubresourceState},
+	structs::{subresource, Extent2D, Extent3D},
 	utility::{self, NameableBuilder},
 };
 use std::sync;
@@ -22,6 +22,10 @@ pub(crate) trait Owner: Send + Sync {
 pub struct Image {
 	dimensions: Extent3D,
 	format: Format,
+	usage: ImageUsage,
+	mip_levels: u32,
+	array_layers: u32,
+	state: Option<sync::Mutex<StateTracker>>,
 	allocation_handle: Option<gpu_allocator::vulkan::Allocation>,
 	internal: backend::vk::Image,
 	owner: Option<sync::Arc<dyn Owner>>, // empty for images created from the swapchain
@@ -34,6 +38,7 @@ impl Image {
 		internal: backend::vk::Image,
 		name: String,
 		format: Format,
+		usage: ImageUsage,
 		dimensions: Extent2D,
 	) -> Image {
 		Image {
@@ -41,6 +46,10 @@ impl Image {
 			internal,
 			allocation_handle: None,
 			format: format,
+			usage,
+			mip_levels: 1,
+			array_layers: 1,
+			state: None,
 			dimensions: Extent3D {
 				width: dimensions.width,
 				height: dimensions.height,
@@ -68,6 +77,10 @@ impl Image {
 			allocation_handle,
 			dimensions: image_info.size(),
 			format: image_info.format(),
+			usage: image_info.usage(),
+			mip_levels: image_info.mip_levels(),
+			array_layers: image_info.array_layers(),
+			state: image_info.state_tracker().map(sync::Mutex::new),
 			name: image_info.name().clone(),
 		}
 	}
@@ -101,6 +114,72 @@ impl Image {
 	pub fn format(&self) -> Format {
 		self.format
 	}
+
+	/// The flags indicating how the image can be used.
+	pub fn usage(&self) -> ImageUsage {
+		self.usage
+	}
+
+	/// Returns an error if the image was not created with all of the provided usage flags.
+	/// `action` describes what the image is being used for, and is included in the error.
+	pub fn require_usage(&self, usage: ImageUsage, action: &str) -> utility::Result<()> {
+		if self.usage.contains(usage) {
+			return Ok(());
+		}
+		Err(utility::Error::InvalidImageUsage(format!(
+			"{:?} cannot be used for {} without {:?} usage",
+			self.name, action, usage
+		)))
+	}
+
+	/// The number of mip levels in the image.
+	pub fn mip_levels(&self) -> u32 {
+		self.mip_levels
+	}
+
+	/// The number of array layers in the image.
+	pub fn array_layers(&self) -> u32 {
+		self.array_layers
+	}
+
+	/// Returns true if the image was [`built`](Builder::with_state_tracking) to track the state of its subresources.
+	pub fn is_state_tracked(&self) -> bool {
+		self.state.is_some()
+	}
+
+	/// Returns the tracked state of a subresource, as of the most recently recorded command.
+	/// Returns None if the image is not tracked or the subresource does not exist.
+	pub fn tracked_state(&self, mip_level: u32, array_layer: u32) -> Option<SubresourceState> {
+		let state = self.state.as_ref()?.lock().unwrap();
+		state.get(mip_level, array_layer)
+	}
+
+	/// Overrides the tracked state of a range of subresources.
+	/// Used when the state of the image changes outside of a [`barrier`](crate::command::barrier),
+	/// such as the final layout of a render pass attachment.
+	pub fn set_tracked_state(&self, range: subresource::Range, state: SubresourceState) {
+		if let Some(tracker) = &self.state {
+			let mips = range.mip_levels(self.mip_levels);
+			let layers = range.array_layers(self.array_layers);
+			tracker.lock().unwrap().set(mips, layers, state);
+		}
+	}
+
+	/// Moves a range of subresources into the `next` state,
+	/// returning the groups of subresources which require a barrier to do so.
+	pub(crate) fn transition_tracked(
+		&self,
+		range: &subresource::Range,
+		next: SubresourceState,
+	) -> utility::Result<Vec<StateChange>> {
+		let tracker = match &self.state {
+			Some(tracker) => tracker,
+			None => return Err(utility::Error::UntrackedResource(self.name.clone())),
+		};
+		let mips = range.mip_levels(self.mip_levels);
+		let layers = range.array_layers(self.array_layers);
+		Ok(tracker.lock().unwrap().transition(mips, layers, next))
+	}
 }
 
 impl std::ops::Deref for Image {
@@ -0,0 +1,360 @@
+use crate::{
+	alloc, buffer,
+	command::{self, barrier},
+	flags::{
+		self, format::Format, Access, BufferUsage, ImageAspect, ImageLayout, ImageUsage,
+		MemoryLocation, PipelineStage, SharingMode,
+	},
+	image::Image,
+	structs::{subresource, Extent3D, Offset3D},
+	utility::{self, NamedObject},
+};
+use std::sync;
+
+/// Copies the pixels of a color [`Image`] (including those created by a swapchain)
+/// into a host-visible [`Buffer`](buffer::Buffer), so they can be read on the CPU.
+///
+/// A readback is used in three steps:
+/// 1. Create the readback via [`new`](Readback::new), which allocates the host-visible buffer.
+/// 2. [`Record`](Readback::record) the copy into a command buffer, and submit that buffer.
+/// 3. After the submitted commands have completed (i.e. the submission's fence is signaled),
+///    read the pixels via [`to_rgba8`](Readback::to_rgba8) or [`write_png`](Readback::write_png).
+///
+/// The readback holds strong references to both the image and the buffer,
+/// so it must be kept alive until the submitted commands have completed.
+pub struct Readback {
+	image: sync::Arc<Image>,
+	buffer: sync::Arc<buffer::Buffer>,
+	size: Extent3D,
+}
+
+impl Readback {
+	/// Allocates a buffer large enough to hold the first layer of the image.
+	///
+	/// The image must have been created with [`TRANSFER_SRC`](ImageUsage::TRANSFER_SRC) usage,
+	/// and its format must be one which can be [`converted`](Readback::to_rgba8) into 8-bit RGBA.
+	pub fn new(
+		allocator: &sync::Arc<alloc::Allocator>,
+		image: &sync::Arc<Image>,
+	) -> anyhow::Result<Self> {
+		use utility::{BuildFromAllocator, NameableBuilder};
+		image.require_usage(ImageUsage::TRANSFER_SRC, "readback")?;
+		let texel_size =
+			texel_size(image.format()).ok_or(utility::Error::UnsupportedFormat(image.format()))?;
+		let size = Extent3D {
+			depth: 1,
+			..image.image_size()
+		};
+		let buffer = buffer::Buffer::builder()
+			.with_name(format!("{}.Readback", image.name()))
+			.with_sharing(SharingMode::EXCLUSIVE)
+			.with_usage(BufferUsage::TRANSFER_DST)
+			.with_size(size.width as usize * size.height as usize * texel_size)
+			.with_location(MemoryLocation::GpuToCpu)
+			.supress_log_on_drop()
+			.build(allocator)?;
+		Ok(Self {
+			image: image.clone(),
+			buffer: sync::Arc::new(buffer),
+			size,
+		})
+	}
+
+	/// The image being read from.
+	pub fn image(&self) -> &sync::Arc<Image> {
+		&self.image
+	}
+
+	/// The host-visible buffer the image is copied into.
+	pub fn buffer(&self) -> &sync::Arc<buffer::Buffer> {
+		&self.buffer
+	}
+
+	/// Records the barriers and copy operation which move the image data into the readback buffer.
+	///
+	/// `layout` is the layout the image is in when the commands execute
+	/// (i.e. [`PresentSrc`](ImageLayout::PresentSrc) for a presented swapchain image).
+	/// The image is transitioned back to that layout once the copy is complete,
+	/// unless the layout was [`Undefined`](ImageLayout::Undefined).
+	///
+	/// Can only be called after [`begin`](command::Buffer::begin) and outside of a render pass.
+	pub fn record(&self, buffer: &command::Buffer, layout: ImageLayout) -> utility::Result<()> {
+		let range = subresource::Range::default().with_aspect(ImageAspect::COLOR);
+		buffer.mark_pipeline_barrier(
+			barrier::Pipeline::new(PipelineStage::AllCommands, PipelineStage::Transfer).with(
+				barrier::Kind::Image(
+					barrier::Image::default()
+						.requires(Access::MemoryWrite)
+						.prevents(Access::TransferRead)
+						.with_image(sync::Arc::downgrade(&self.image))
+						.with_layout(layout, ImageLayout::TransferSrcOptimal)
+						.with_range(range),
+				),
+			),
+		);
+		buffer.copy_image_to_buffer(
+			&self.image,
+			ImageLayout::TransferSrcOptimal,
+			&self.buffer,
+			vec![command::CopyImageToBuffer {
+				layers: subresource::Layers::default().with_aspect(ImageAspect::COLOR),
+				offset: Offset3D::default(),
+				size: self.size,
+				buffer_offset: 0,
+			}],
+		)?;
+		// Make the transfer writes visible to the CPU once the submission has completed.
+		buffer.mark_pipeline_barrier(
+			barrier::Pipeline::new(PipelineStage::Transfer, PipelineStage::Host).with(
+				barrier::Kind::Buffer(
+					barrier::Buffer::new(&self.buffer)
+						.requires(Access::TransferWrite)
+						.prevents(Access::HostRead),
+				),
+			),
+		);
+		if layout != ImageLayout::Undefined && layout != ImageLayout::Preinitialized {
+			buffer.mark_pipeline_barrier(
+				barrier::Pipeline::new(PipelineStage::Transfer, PipelineStage::AllCommands).with(
+					barrier::Kind::Image(
+						barrier::Image::default()
+							.requires(Access::TransferRead)
+							.prevents(Access::MemoryRead)
+							.prevents(Access::MemoryWrite)
+							.with_image(sync::Arc::downgrade(&self.image))
+							.with_layout(ImageLayout::TransferSrcOptimal, layout)
+							.with_range(range),
+					),
+				),
+			);
+		}
+		Ok(())
+	}
+
+	/// Returns the pixels of the image as tightly packed 8-bit RGBA values.
+	///
+	/// BGRA formats are swizzled, and float formats are treated as linear values which are clamped
+	/// and encoded as sRGB. All other 8-bit formats are returned as-is, so an sRGB image results in sRGB pixels.
+	///
+	/// Can only be called once the commands [`recorded`](Readback::record) have completed on the GPU.
+	pub fn to_rgba8(&self) -> utility::Result<Vec<u8>> {
+		let mapped = self.buffer.handle().mapped_slice().ok_or_else(|| {
+			utility::Error::InvalidBufferFormat("Readback buffer is not mapped".to_owned())
+		})?;
+		convert_mapped(self.image.format(), mapped, self.buffer.size())
+	}
+
+	/// Encodes the [`pixels`](Readback::to_rgba8) of the image as a PNG.
+	pub fn write_png<W: std::io::Write>(&self, writer: W) -> anyhow::Result<()> {
+		let pixels = self.to_rgba8()?;
+		let mut encoder = png::Encoder::new(writer, self.size.width, self.size.height);
+		encoder.set_color(png::ColorType::Rgba);
+		encoder.set_depth(png::BitDepth::Eight);
+		let mut writer = encoder.write_header()?;
+		writer.write_image_data(&pixels)?;
+		Ok(())
+	}
+
+	/// Saves the [`pixels`](Readback::to_rgba8) of the image to a PNG file.
+	pub fn save_png(&self, path: impl AsRef<std::path::Path>) -> anyhow::Result<()> {
+		let file = std::fs::File::create(path)?;
+		self.write_png(std::io::BufWriter::new(file))
+	}
+}
+
+/// Returns the number of bytes per texel for formats which can be read back.
+fn texel_size(format: Format) -> Option<usize> {
+	match format {
+		Format::R8G8B8A8_UNORM
+		| Format::R8G8B8A8_SRGB
+		| Format::B8G8R8A8_UNORM
+		| Format::B8G8R8A8_SRGB
+		| Format::A8B8G8R8_UNORM_PACK32
+		| Format::A8B8G8R8_SRGB_PACK32
+		| Format::A2R10G10B10_UNORM_PACK32
+		| Format::A2B10G10R10_UNORM_PACK32
+		| Format::R16G16B16A16_SFLOAT
+		| Format::R32G32B32A32_SFLOAT => flags::format::metadata(format).map(|info| info.block_size),
+		_ => None,
+	}
+}
+
+/// Converts the first `size` bytes of a mapped allocation into 8-bit RGBA texels.
+/// The allocation is often larger than the buffer (i.e. padded to the allocator's alignment),
+/// and the bytes past the end of the buffer are not part of the image.
+fn convert_mapped(format: Format, mapped: &[u8], size: usize) -> utility::Result<Vec<u8>> {
+	let bytes = mapped.get(..size).ok_or_else(|| {
+		utility::Error::InvalidBufferFormat(format!(
+			"Readback buffer maps {} bytes, but the image requires {}",
+			mapped.len(),
+			size
+		))
+	})?;
+	convert_to_rgba8(format, bytes).ok_or(utility::Error::UnsupportedFormat(format))
+}
+
+/// Converts tightly packed texels of a given format into 8-bit RGBA texels.
+fn convert_to_rgba8(format: Format, bytes: &[u8]) -> Option<Vec<u8>> {
+	let texels = bytes.chunks_exact(texel_size(format)?);
+	let mut pixels = Vec::with_capacity(texels.len() * 4);
+	for texel in texels {
+		let rgba = match format {
+			Format::R8G8B8A8_UNORM
+			| Format::R8G8B8A8_SRGB
+			| Format::A8B8G8R8_UNORM_PACK32
+			| Format::A8B8G8R8_SRGB_PACK32 => [texel[0], texel[1], texel[2], texel[3]],
+			Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB => {
+				[texel[2], texel[1], texel[0], texel[3]]
+			}
+			Format::A2R10G10B10_UNORM_PACK32 | Format::A2B10G10R10_UNORM_PACK32 => {
+				let packed = u32::from_le_bytes([texel[0], texel[1], texel[2], texel[3]]);
+				let low = ((packed & 0x3ff) >> 2) as u8;
+				let mid = (((packed >> 10) & 0x3ff) >> 2) as u8;
+				let high = (((packed >> 20) & 0x3ff) >> 2) as u8;
+				let alpha = ((packed >> 30) * 85) as u8;
+				match format {
+					Format::A2R10G10B10_UNORM_PACK32 => [high, mid, low, alpha],
+					_ => [low, mid, high, alpha],
+				}
+			}
+			Format::R16G16B16A16_SFLOAT => {
+				let channel = |i: usize| f16_to_f32(u16::from_le_bytes([texel[i], texel[i + 1]]));
+				[
+					linear_to_srgb8(channel(0)),
+					linear_to_srgb8(channel(2)),
+					linear_to_srgb8(channel(4)),
+					unorm8(channel(6)),
+				]
+			}
+			Format::R32G32B32A32_SFLOAT => {
+				let channel = |i: usize| {
+					f32::from_le_bytes([texel[i], texel[i + 1], texel[i + 2], texel[i + 3]])
+				};
+				[
+					linear_to_srgb8(channel(0)),
+					linear_to_srgb8(channel(4)),
+					linear_to_srgb8(channel(8)),
+					unorm8(channel(12)),
+				]
+			}
+			_ => return None,
+		};
+		pixels.extend_from_slice(&rgba);
+	}
+	Some(pixels)
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+	let exponent = ((bits >> 10) & 0x1f) as u32;
+	let mantissa = (bits & 0x3ff) as u32;
+	let magnitude = match exponent {
+		// zero or subnormal
+		0 => mantissa as f32 * (2.0f32).powi(-24),
+		// infinity or NaN
+		0x1f if mantissa == 0 => f32::INFINITY,
+		0x1f => f32::NAN,
+		_ => f32::from_bits(((exponent + 112) << 23) | (mantissa << 13)),
+	};
+	match bits & 0x8000 {
+		0 => magnitude,
+		_ => -magnitude,
+	}
+}
+
+fn saturate(value: f32) -> f32 {
+	match value.is_nan() {
+		true => 0.0,
+		false => value.clamp(0.0, 1.0),
+	}
+}
+
+fn unorm8(value: f32) -> u8 {
+	(saturate(value) * 255.0).round() as u8
+}
+
+fn linear_to_srgb8(value: f32) -> u8 {
+	let value = saturate(value);
+	unorm8(match value <= 0.0031308 {
+		true => value * 12.92,
+		false => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+	})
+}
+
+#[cfg(test)]
+mod rgba8_conversion {
+	use super::*;
+
+	#[test]
+	fn rgba_is_unchanged() {
+		let bytes = [1, 2, 3, 4, 5, 6, 7, 8];
+		assert_eq!(
+			convert_to_rgba8(Format::R8G8B8A8_SRGB, &bytes),
+			Some(bytes.to_vec())
+		);
+	}
+
+	#[test]
+	fn bgra_is_swizzled() {
+		assert_eq!(
+			convert_to_rgba8(Format::B8G8R8A8_UNORM, &[10, 20, 30, 40]),
+			Some(vec![30, 20, 10, 40])
+		);
+	}
+
+	#[test]
+	fn packed_10bit() {
+		let packed: u32 = (3 << 30) | (0x3ff << 20) | (0x200 << 10);
+		assert_eq!(
+			convert_to_rgba8(Format::A2B10G10R10_UNORM_PACK32, &packed.to_le_bytes()),
+			Some(vec![0, 128, 255, 255])
+		);
+		assert_eq!(
+			convert_to_rgba8(Format::A2R10G10B10_UNORM_PACK32, &packed.to_le_bytes()),
+			Some(vec![255, 128, 0, 255])
+		);
+	}
+
+	#[test]
+	fn half_float() {
+		assert_eq!(f16_to_f32(0x0000), 0.0);
+		assert_eq!(f16_to_f32(0x3c00), 1.0);
+		assert_eq!(f16_to_f32(0x3800), 0.5);
+		assert_eq!(f16_to_f32(0xc000), -2.0);
+		assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+		assert!(f16_to_f32(0x7e00).is_nan());
+		// 1.0, 0.0, 2.0 (clamped), 0.5
+		let bytes = [0x00, 0x3c, 0x00, 0x00, 0x00, 0x40, 0x00, 0x38];
+		assert_eq!(
+			convert_to_rgba8(Format::R16G16B16A16_SFLOAT, &bytes),
+			Some(vec![255, 0, 255, 128])
+		);
+	}
+
+	#[test]
+	fn float_is_srgb_encoded() {
+		let bytes = [0.5f32, -1.0, f32::NAN, 1.0]
+			.iter()
+			.flat_map(|value| value.to_le_bytes())
+			.collect::<Vec<_>>();
+		assert_eq!(
+			convert_to_rgba8(Format::R32G32B32A32_SFLOAT, &bytes),
+			Some(vec![188, 0, 0, 255])
+		);
+	}
+
+	#[test]
+	fn padded_allocation_is_trimmed() {
+		let mut mapped = vec![10, 20, 30, 40, 50, 60, 70, 80];
+		mapped.resize(256, 0xff);
+		assert_eq!(
+			convert_mapped(Format::B8G8R8A8_UNORM, &mapped, 8).unwrap(),
+			vec![30, 20, 10, 40, 70, 60, 50, 80]
+		);
+		assert!(convert_mapped(Format::B8G8R8A8_UNORM, &mapped[..4], 8).is_err());
+	}
+
+	#[test]
+	fn unsupported() {
+		assert_eq!(convert_to_rgba8(Format::D32_SFLOAT, &[0; 4]), None);
+	}
+}
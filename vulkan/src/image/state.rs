@@ -0,0 +1,173 @@
+use crate::{flags::ImageLayout, utility::AccessState};
+use std::ops::Range;
+
+/// The layout and most recent access of a single subresource (mip level & array layer) of an [`Image`](super::Image).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SubresourceState {
+	pub layout: ImageLayout,
+	pub access: AccessState,
+}
+
+impl SubresourceState {
+	pub fn new(layout: ImageLayout, access: AccessState) -> Self {
+		Self { layout, access }
+	}
+
+	/// Returns true if a barrier must be placed between this state and the `next` state,
+	/// either because the layout changes or because the accesses conflict.
+	pub fn requires_barrier(&self, next: &Self) -> bool {
+		self.layout != next.layout || self.access.conflicts_with(&next.access)
+	}
+}
+
+/// A set of subresources which were all in the same state before a transition,
+/// and therefore can be moved to the next state by a single barrier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StateChange {
+	pub mip_level: u32,
+	pub array_layers: Range<u32>,
+	pub prev: SubresourceState,
+}
+
+/// The state of every subresource of an image, as of the most recently recorded command.
+pub(crate) struct StateTracker {
+	array_layers: u32,
+	states: Vec<SubresourceState>,
+}
+
+impl StateTracker {
+	pub fn new(mip_levels: u32, array_layers: u32, layout: ImageLayout) -> Self {
+		let initial = SubresourceState::new(layout, AccessState::default());
+		Self {
+			array_layers,
+			states: vec![initial; (mip_levels * array_layers) as usize],
+		}
+	}
+
+	fn index(&self, mip_level: u32, array_layer: u32) -> usize {
+		(mip_level * self.array_layers + array_layer) as usize
+	}
+
+	pub fn get(&self, mip_level: u32, array_layer: u32) -> Option<SubresourceState> {
+		if array_layer >= self.array_layers {
+			return None;
+		}
+		self.states.get(self.index(mip_level, array_layer)).copied()
+	}
+
+	pub fn set(
+		&mut self,
+		mip_levels: Range<u32>,
+		array_layers: Range<u32>,
+		state: SubresourceState,
+	) {
+		for mip_level in mip_levels {
+			for array_layer in array_layers.clone() {
+				let index = self.index(mip_level, array_layer);
+				self.states[index] = state;
+			}
+		}
+	}
+
+	/// Moves the subresources into the `next` state, returning the changes which require a barrier.
+	/// Adjacent array layers which were in the same state are grouped into a single change.
+	/// Subresources which only add reads to other reads in the same layout do not need a barrier,
+	/// and instead accumulate the accesses so that a later write waits on all of them.
+	pub fn transition(
+		&mut self,
+		mip_levels: Range<u32>,
+		array_layers: Range<u32>,
+		next: SubresourceState,
+	) -> Vec<StateChange> {
+		let mut changes: Vec<StateChange> = Vec::new();
+		for mip_level in mip_levels {
+			for array_layer in array_layers.clone() {
+				let index = self.index(mip_level, array_layer);
+				let prev = self.states[index];
+				if !prev.requires_barrier(&next) {
+					self.states[index].access = prev.access.merge(&next.access);
+					continue;
+				}
+				self.states[index] = next;
+				match changes.last_mut() {
+					Some(change)
+						if change.mip_level == mip_level
+							&& change.array_layers.end == array_layer
+							&& change.prev == prev =>
+					{
+						change.array_layers.end += 1;
+					}
+					_ => changes.push(StateChange {
+						mip_level,
+						array_layers: array_layer..array_layer + 1,
+						prev,
+					}),
+				}
+			}
+		}
+		changes
+	}
+}
+
+#[cfg(test)]
+mod state_tracking {
+	use super::*;
+	use crate::flags::{Access, PipelineStage, StageSet};
+
+	fn sampled() -> SubresourceState {
+		SubresourceState::new(
+			ImageLayout::ShaderReadOnlyOptimal,
+			AccessState::new(PipelineStage::FragmentShader, Access::ShaderRead),
+		)
+	}
+
+	fn transfer_dst() -> SubresourceState {
+		SubresourceState::new(
+			ImageLayout::TransferDstOptimal,
+			AccessState::new(PipelineStage::Transfer, Access::TransferWrite),
+		)
+	}
+
+	#[test]
+	fn groups_adjacent_layers() {
+		let mut tracker = StateTracker::new(2, 4, ImageLayout::Undefined);
+		let changes = tracker.transition(0..2, 0..4, transfer_dst());
+		assert_eq!(changes.len(), 2);
+		assert_eq!(changes[0].mip_level, 0);
+		assert_eq!(changes[0].array_layers, 0..4);
+		assert_eq!(changes[0].prev.layout, ImageLayout::Undefined);
+		assert_eq!(changes[1].mip_level, 1);
+		assert_eq!(tracker.get(1, 3), Some(transfer_dst()));
+	}
+
+	#[test]
+	fn splits_differing_layers() {
+		let mut tracker = StateTracker::new(1, 4, ImageLayout::Undefined);
+		tracker.transition(0..1, 1..3, transfer_dst());
+		let changes = tracker.transition(0..1, 0..4, sampled());
+		let layers = changes
+			.iter()
+			.map(|change| change.array_layers.clone())
+			.collect::<Vec<_>>();
+		assert_eq!(layers, vec![0..1, 1..3, 3..4]);
+		assert_eq!(changes[1].prev, transfer_dst());
+	}
+
+	#[test]
+	fn reads_after_reads_merge() {
+		let mut tracker = StateTracker::new(1, 1, ImageLayout::Undefined);
+		assert_eq!(tracker.transition(0..1, 0..1, sampled()).len(), 1);
+		let compute_read = SubresourceState::new(
+			ImageLayout::ShaderReadOnlyOptimal,
+			AccessState::new(PipelineStage::ComputerShader, Access::ShaderRead),
+		);
+		assert!(tracker.transition(0..1, 0..1, compute_read).is_empty());
+		let merged = tracker.get(0, 0).unwrap();
+		assert_eq!(
+			merged.access.stages,
+			StageSet::from(PipelineStage::FragmentShader) | PipelineStage::ComputerShader
+		);
+		let changes = tracker.transition(0..1, 0..1, transfer_dst());
+		assert_eq!(changes[0].prev, merged);
+	}
+}
@@ -81,5 +81,9 @@ pub mod structs;
 #[path = "utility/_.rs"]
 pub mod utility;
 
+/// Structs for querying information from the GPU about the commands it executes (i.e. timestamps).
+#[path = "query/_.rs"]
+pub mod query;
+
 #[path = "procedure.rs"]
 pub mod procedure;
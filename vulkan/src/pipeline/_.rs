@@ -1,6 +1,9 @@
 mod builder;
 pub use builder::*;
 
+mod compute_builder;
+pub use compute_builder::*;
+
 /// Structures for creating a pipeline layout object.
 pub mod layout;
 
@@ -10,16 +13,26 @@ pub use pipeline::*;
 /// Structures around the various properties about a pipeline that are used in the builder.
 pub mod state;
 
-#[derive(Clone)]
-pub struct PushConstantRange(crate::flags::ShaderKind, usize, usize);
-impl Default for PushConstantRange {
-	fn default() -> Self {
-		Self(crate::flags::ShaderKind::Vertex, 0, 0)
-	}
-}
+/// A range of bytes in a pipeline's push constants, and the shader stages which can access them.
+///
+/// The range is accessed by the [`Vertex`](crate::flags::ShaderKind::Vertex) stage
+/// until any stages are provided.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PushConstantRange(
+	Option<enumset::EnumSet<crate::flags::ShaderKind>>,
+	usize,
+	usize,
+);
 impl PushConstantRange {
+	/// Adds a stage which can access the range.
 	pub fn with_stage(mut self, stage: crate::flags::ShaderKind) -> Self {
-		self.0 = stage;
+		self.0 = Some(self.0.unwrap_or_default() | stage);
+		self
+	}
+	/// Sets all of the stages which can access the range.
+	/// The set must not be empty when the [`layout`](layout::Layout) is built.
+	pub fn with_stage_set(mut self, stages: enumset::EnumSet<crate::flags::ShaderKind>) -> Self {
+		self.0 = Some(stages);
 		self
 	}
 	pub fn with_offset(mut self, offset: usize) -> Self {
@@ -30,11 +43,21 @@ impl PushConstantRange {
 		self.2 = size;
 		self
 	}
+	pub fn stages(&self) -> enumset::EnumSet<crate::flags::ShaderKind> {
+		self.0
+			.unwrap_or_else(|| crate::flags::ShaderKind::Vertex.into())
+	}
+	pub fn offset(&self) -> usize {
+		self.1
+	}
+	pub fn size(&self) -> usize {
+		self.2
+	}
 }
 impl Into<crate::backend::vk::PushConstantRange> for PushConstantRange {
 	fn into(self) -> crate::backend::vk::PushConstantRange {
 		crate::backend::vk::PushConstantRange::builder()
-			.stage_flags(self.0.into())
+			.stage_flags(crate::flags::ShaderKind::fold(&self.stages()))
 			.offset(self.1 as u32)
 			.size(self.2 as u32)
 			.build()
@@ -154,7 +154,13 @@ impl Builder {
 				_ => Err(utility::Error::VulkanError(vk_result)),
 			},
 		}?;
-		let pipeline = Pipeline::from(device.clone(), pipelines[0], self.name.clone());
+		let pipeline = Pipeline::from(
+			device.clone(),
+			pipelines[0],
+			self.name.clone(),
+			flags::PipelineBindPoint::GRAPHICS,
+			self.dynamic_state.states().clone(),
+		);
 		device.set_object_name_logged(&pipeline.create_name(self.name.as_str()));
 		Ok(pipeline)
 	}
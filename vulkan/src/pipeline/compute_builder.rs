@@ -0,0 +1,103 @@
+use crate::{
+	backend,
+	device::logical,
+	flags,
+	pipeline::{layout, Pipeline},
+	shader, utility,
+};
+
+use std::sync;
+
+/// Information used to construct a compute [`Pipeline`](Pipeline).
+///
+/// Unlike graphics pipelines, compute pipelines are not tied to a [`Render Pass`](crate::renderpass::Pass),
+/// and are bound with [`PipelineBindPoint::COMPUTE`](flags::PipelineBindPoint::COMPUTE).
+#[derive(Default)]
+pub struct ComputeBuilder {
+	shader: Option<sync::Weak<shader::Module>>,
+	allow_dispatch_base: bool,
+	name: String,
+}
+
+impl ComputeBuilder {
+	/// Sets the [`compute`](flags::ShaderKind::Compute) shader the pipeline executes.
+	pub fn with_shader(mut self, shader: sync::Weak<shader::Module>) -> Self {
+		self.shader = Some(shader);
+		self
+	}
+
+	/// Allows the pipeline to be used with [`dispatch_base`](crate::command::Buffer::dispatch_base).
+	pub fn with_dispatch_base(mut self) -> Self {
+		self.allow_dispatch_base = true;
+		self
+	}
+
+	/// Creates the actual [`Pipeline`](Pipeline) object.
+	pub fn build(
+		self,
+		device: sync::Arc<logical::Device>,
+		layout: &layout::Layout,
+	) -> Result<Pipeline, utility::Error> {
+		use utility::HandledObject;
+
+		let module = match self.shader.as_ref().and_then(sync::Weak::upgrade) {
+			Some(module) => module,
+			None => {
+				return Err(utility::Error::InvalidPipeline(format!(
+					"{:?} requires a compute shader which has not been dropped",
+					self.name
+				)))
+			}
+		};
+		if module.kind() != flags::ShaderKind::Compute {
+			return Err(utility::Error::InvalidPipeline(format!(
+				"{:?} requires a compute shader, but was given a {:?} shader",
+				self.name,
+				module.kind()
+			)));
+		}
+
+		let stage = backend::vk::PipelineShaderStageCreateInfo::builder()
+			.stage(module.kind().into())
+			.module(**module)
+			.name(module.entry_point())
+			.build();
+		let info = backend::vk::ComputePipelineCreateInfo::builder()
+			.flags(match self.allow_dispatch_base {
+				true => backend::vk::PipelineCreateFlags::DISPATCH_BASE,
+				false => backend::vk::PipelineCreateFlags::empty(),
+			})
+			.stage(stage)
+			.layout(**layout)
+			.build();
+
+		let pipelines = match unsafe {
+			device.create_compute_pipelines(backend::vk::PipelineCache::null(), &[info], None)
+		} {
+			Ok(pipelines) => Ok(pipelines),
+			Err((pipelines, vk_result)) => match vk_result {
+				backend::vk::Result::SUCCESS => Ok(pipelines),
+				_ => Err(utility::Error::VulkanError(vk_result)),
+			},
+		}?;
+		let pipeline = Pipeline::from(
+			device.clone(),
+			pipelines[0],
+			self.name.clone(),
+			flags::PipelineBindPoint::COMPUTE,
+			Vec::new(),
+		);
+		device.set_object_name_logged(&pipeline.create_name(self.name.as_str()));
+		Ok(pipeline)
+	}
+}
+
+impl utility::NameableBuilder for ComputeBuilder {
+	fn set_name(&mut self, name: impl Into<String>) {
+		self.name = name.into();
+	}
+
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
@@ -1,11 +1,15 @@
-use crate::{backend, descriptor::layout::SetLayout, device::logical, utility};
-use std::sync;
+use crate::{
+	backend, descriptor::layout::SetLayout, device::logical, flags, pipeline::PushConstantRange,
+	utility,
+};
+use enumset::EnumSet;
+use std::{marker::PhantomData, sync};
 
 /// The builder for a pipeline [`Layout`].
 #[derive(Clone)]
 pub struct Builder {
 	descriptor_layouts: Vec<sync::Weak<SetLayout>>,
-	push_constant_ranges: Vec<super::PushConstantRange>,
+	push_constant_ranges: Vec<PushConstantRange>,
 	name: String,
 }
 
@@ -33,12 +37,12 @@ impl Builder {
 		self.descriptor_layouts.push(sync::Arc::downgrade(layout));
 	}
 
-	pub fn with_push_constant_range(mut self, range: super::PushConstantRange) -> Self {
+	pub fn with_push_constant_range(mut self, range: PushConstantRange) -> Self {
 		self.add_push_constant_range(range);
 		self
 	}
 
-	pub fn add_push_constant_range(&mut self, range: super::PushConstantRange) {
+	pub fn add_push_constant_range(&mut self, range: PushConstantRange) {
 		self.push_constant_ranges.push(range);
 	}
 }
@@ -56,6 +60,19 @@ impl utility::NameableBuilder for Builder {
 impl utility::BuildFromDevice for Builder {
 	type Output = Layout;
 	fn build(self, device: &sync::Arc<logical::Device>) -> anyhow::Result<Self::Output> {
+		if let Some(range) = self
+			.push_constant_ranges
+			.iter()
+			.find(|range| range.stages().is_empty())
+		{
+			return Err(utility::Error::InvalidPushConstant(format!(
+				"{:?} has a push constant range at {}..{} with no stages",
+				self.name,
+				range.offset(),
+				range.offset() + range.size()
+			))
+			.into());
+		}
 		let vk_descriptor_layouts = self
 			.descriptor_layouts
 			.iter()
@@ -75,6 +92,7 @@ impl utility::BuildFromDevice for Builder {
 			internal: unsafe { device.create_pipeline_layout(&vk_info, None) }?,
 			device: device.clone(),
 			name: self.name.clone(),
+			push_constant_ranges: self.push_constant_ranges.clone(),
 		};
 		self.set_object_name(device, &layout);
 		Ok(layout)
@@ -90,12 +108,149 @@ pub struct Layout {
 	internal: backend::vk::PipelineLayout,
 	device: sync::Arc<logical::Device>,
 	name: String,
+	push_constant_ranges: Vec<PushConstantRange>,
 }
 
 impl Layout {
 	pub fn builder() -> Builder {
 		Builder::default()
 	}
+
+	/// The push constant ranges that the layout was created with.
+	pub fn push_constant_ranges(&self) -> &Vec<PushConstantRange> {
+		&self.push_constant_ranges
+	}
+
+	/// Checks that `size` bytes of push constants at `offset` can be pushed for `stages`.
+	///
+	/// The bytes must be a multiple of 4 in offset and size, fit within the device's
+	/// [`max_push_constants_size`](crate::device::physical::Device::max_push_constants_size),
+	/// be covered for every stage by the layout's ranges, and `stages` must include
+	/// every stage of any range that the bytes overlap.
+	pub fn validate_push_constant(
+		&self,
+		stages: EnumSet<flags::ShaderKind>,
+		offset: usize,
+		size: usize,
+	) -> utility::Result<()> {
+		let max_size = self.device.physical().max_push_constants_size() as usize;
+		validate_push_constant(&self.push_constant_ranges, max_size, stages, offset, size)
+			.map_err(|err| utility::Error::InvalidPushConstant(format!("{:?} {}", self.name, err)))
+	}
+
+	/// Creates a typed handle for pushing a `T` at `offset` to `stages`,
+	/// validating the block against the layout once instead of on every push.
+	pub fn push_constants<T>(
+		&self,
+		stages: impl Into<EnumSet<flags::ShaderKind>>,
+		offset: usize,
+	) -> utility::Result<PushConstants<'_, T>>
+	where
+		T: Sized + bytemuck::Pod,
+	{
+		let stages = stages.into();
+		self.validate_push_constant(stages, offset, std::mem::size_of::<T>())?;
+		Ok(PushConstants {
+			layout: self,
+			stages,
+			offset,
+			marker: PhantomData,
+		})
+	}
+}
+
+/// A block of push constants of type `T`, which has been validated against a pipeline [`Layout`].
+/// Created by [`Layout::push_constants`] and recorded with
+/// [`command::Buffer::push_constants`](crate::command::Buffer::push_constants).
+///
+/// The block borrows the layout it was created from, so it cannot outlive it.
+pub struct PushConstants<'a, T> {
+	layout: &'a Layout,
+	stages: EnumSet<flags::ShaderKind>,
+	offset: usize,
+	marker: PhantomData<T>,
+}
+
+impl<'a, T> PushConstants<'a, T> {
+	pub fn stages(&self) -> EnumSet<flags::ShaderKind> {
+		self.stages
+	}
+
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	pub fn layout(&self) -> &'a Layout {
+		self.layout
+	}
+}
+
+impl<'a, T> Clone for PushConstants<'a, T> {
+	fn clone(&self) -> Self {
+		Self {
+			layout: self.layout,
+			stages: self.stages,
+			offset: self.offset,
+			marker: PhantomData,
+		}
+	}
+}
+
+fn validate_push_constant(
+	ranges: &[PushConstantRange],
+	max_size: usize,
+	stages: EnumSet<flags::ShaderKind>,
+	offset: usize,
+	size: usize,
+) -> Result<(), String> {
+	let end = offset + size;
+	if stages.is_empty() {
+		return Err("push constants must be pushed to at least one stage".to_owned());
+	}
+	if size == 0 || !offset.is_multiple_of(4) || !size.is_multiple_of(4) {
+		return Err(format!(
+			"push constants at {}..{} must have a non-zero size, and an offset and size which are multiples of 4",
+			offset, end
+		));
+	}
+	if end > max_size {
+		return Err(format!(
+			"push constants at {}..{} exceed the device limit of {} bytes",
+			offset, end, max_size
+		));
+	}
+	for stage in stages.iter() {
+		let mut covered = ranges
+			.iter()
+			.filter(|range| range.stages().contains(stage))
+			.map(|range| (range.offset(), range.offset() + range.size()))
+			.collect::<Vec<_>>();
+		covered.sort_unstable();
+		let mut cursor = offset;
+		for (range_start, range_end) in covered {
+			if range_start <= cursor && range_end > cursor {
+				cursor = range_end;
+			}
+		}
+		if cursor < end {
+			return Err(format!(
+				"has no push constant range for {:?} covering bytes {}..{}",
+				stage, cursor, end
+			));
+		}
+	}
+	for range in ranges.iter() {
+		let overlaps = range.offset() < end && offset < range.offset() + range.size();
+		if overlaps && !stages.is_superset(range.stages()) {
+			return Err(format!(
+				"push constants at {}..{} overlap a range which is also used by {:?}, so they must be pushed to all of its stages",
+				offset,
+				end,
+				range.stages() - stages
+			));
+		}
+	}
+	Ok(())
 }
 
 impl std::ops::Deref for Layout {
@@ -116,6 +271,12 @@ impl Drop for Layout {
 	}
 }
 
+impl utility::NamedObject for Layout {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
 impl utility::HandledObject for Layout {
 	fn kind(&self) -> backend::vk::ObjectType {
 		<backend::vk::PipelineLayout as backend::vk::Handle>::TYPE
@@ -126,3 +287,59 @@ impl utility::HandledObject for Layout {
 		self.internal.as_raw()
 	}
 }
+
+#[cfg(test)]
+mod push_constants {
+	use super::*;
+	use flags::ShaderKind::{Fragment, Vertex};
+
+	fn ranges() -> Vec<PushConstantRange> {
+		vec![
+			PushConstantRange::default()
+				.with_stage(Vertex)
+				.with_offset(0)
+				.with_size(64),
+			PushConstantRange::default()
+				.with_stage(Vertex)
+				.with_stage(Fragment)
+				.with_offset(64)
+				.with_size(16),
+		]
+	}
+
+	#[test]
+	fn within_declared_ranges() {
+		assert!(validate_push_constant(&ranges(), 128, Vertex.into(), 0, 64).is_ok());
+		assert!(validate_push_constant(&ranges(), 128, Vertex | Fragment, 64, 16).is_ok());
+	}
+
+	#[test]
+	fn must_be_covered_for_every_stage() {
+		assert!(validate_push_constant(&ranges(), 128, Fragment.into(), 0, 16).is_err());
+		assert!(validate_push_constant(&ranges(), 128, Vertex.into(), 48, 48).is_err());
+	}
+
+	#[test]
+	fn must_include_all_stages_of_overlapped_ranges() {
+		assert!(validate_push_constant(&ranges(), 128, Vertex.into(), 64, 16).is_err());
+	}
+
+	#[test]
+	fn ranges_default_to_the_vertex_stage() {
+		assert_eq!(PushConstantRange::default().stages(), Vertex);
+		assert_eq!(
+			PushConstantRange::default().with_stage(Fragment).stages(),
+			Fragment
+		);
+		assert!(PushConstantRange::default()
+			.with_stage_set(EnumSet::empty())
+			.stages()
+			.is_empty());
+	}
+
+	#[test]
+	fn must_fit_device_limit_and_alignment() {
+		assert!(validate_push_constant(&ranges(), 32, Vertex.into(), 0, 64).is_err());
+		assert!(validate_push_constant(&ranges(), 128, Vertex.into(), 2, 4).is_err());
+	}
+}
@@ -1,13 +1,21 @@
-use crate::{backend, device::logical, pipeline::Builder, utility};
+use crate::{
+	backend,
+	device::logical,
+	flags,
+	pipeline::{Builder, ComputeBuilder},
+	utility,
+};
 
 use std::sync;
 
-/// A vulkan Pipeline. A given pipeline is only valid for a specific [`Render Pass`](crate::renderpass::Pass),
-/// and is used to issue commands to the [`GPU`](crate::device::physical::Device).
+/// A vulkan Pipeline. A given graphics pipeline is only valid for a specific [`Render Pass`](crate::renderpass::Pass),
+/// while compute pipelines can be used outside of render passes. Pipelines are used to issue commands to the [`GPU`](crate::device::physical::Device).
 pub struct Pipeline {
 	internal: backend::vk::Pipeline,
 	device: sync::Arc<logical::Device>,
 	name: String,
+	bind_point: flags::PipelineBindPoint,
+	dynamic_states: Vec<flags::DynamicState>,
 }
 
 impl Pipeline {
@@ -15,17 +23,41 @@ impl Pipeline {
 		Builder::default()
 	}
 
+	pub fn compute_builder() -> ComputeBuilder {
+		ComputeBuilder::default()
+	}
+
 	pub(crate) fn from(
 		device: sync::Arc<logical::Device>,
 		internal: backend::vk::Pipeline,
 		name: String,
+		bind_point: flags::PipelineBindPoint,
+		dynamic_states: Vec<flags::DynamicState>,
 	) -> Pipeline {
 		Pipeline {
 			device,
 			internal,
 			name,
+			bind_point,
+			dynamic_states,
 		}
 	}
+
+	/// The bind point the pipeline must be bound to (i.e. graphics or compute).
+	pub fn bind_point(&self) -> flags::PipelineBindPoint {
+		self.bind_point
+	}
+
+	/// The states which are set via a [`command buffer`](crate::command::Buffer) while the pipeline is bound,
+	/// instead of being baked into the pipeline.
+	pub fn dynamic_states(&self) -> &Vec<flags::DynamicState> {
+		&self.dynamic_states
+	}
+
+	/// Returns true if the pipeline declared `state` as [`dynamic`](crate::pipeline::state::Dynamic).
+	pub fn is_dynamic(&self, state: flags::DynamicState) -> bool {
+		self.dynamic_states.contains(&state)
+	}
 }
 
 impl std::ops::Deref for Pipeline {
@@ -42,6 +74,12 @@ impl Drop for Pipeline {
 	}
 }
 
+impl utility::NamedObject for Pipeline {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
 impl utility::HandledObject for Pipeline {
 	fn kind(&self) -> backend::vk::ObjectType {
 		<backend::vk::Pipeline as backend::vk::Handle>::TYPE
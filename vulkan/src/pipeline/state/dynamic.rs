@@ -20,6 +20,15 @@ impl Dynamic {
 		self
 	}
 
+	/// Returns true if the state will be set dynamically via a command buffer.
+	pub fn contains(&self, state: flags::DynamicState) -> bool {
+		self.states.contains(&state)
+	}
+
+	pub(crate) fn states(&self) -> &Vec<flags::DynamicState> {
+		&self.states
+	}
+
 	pub(crate) fn as_vk(&self) -> backend::vk::PipelineDynamicStateCreateInfo {
 		backend::vk::PipelineDynamicStateCreateInfo::builder()
 			.dynamic_states(&self.states[..])
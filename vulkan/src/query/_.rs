@@ -0,0 +1,5 @@
+mod pool;
+pub use pool::*;
+
+mod profiler;
+pub use profiler::*;
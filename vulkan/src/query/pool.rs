@@ -0,0 +1,318 @@
+use crate::{
+	backend,
+	device::logical,
+	flags,
+	utility::{self, HandledObject},
+};
+use std::{ops::Range, sync};
+
+/// The kind of queries in a [`Pool`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+	/// Records the GPU time at which a pipeline stage completes,
+	/// written by [`write_timestamp`](crate::command::Buffer::write_timestamp).
+	Timestamp,
+	/// Counts the number of samples which pass the depth and stencil tests
+	/// between [`begin_query`](crate::command::Buffer::begin_query) and [`end_query`](crate::command::Buffer::end_query).
+	Occlusion,
+	/// Counts the enabled statistics (i.e. the number of fragment shader invocations)
+	/// between [`begin_query`](crate::command::Buffer::begin_query) and [`end_query`](crate::command::Buffer::end_query).
+	PipelineStatistics(flags::QueryPipelineStatistic),
+}
+
+impl Kind {
+	fn as_vk(&self) -> backend::vk::QueryType {
+		match self {
+			Self::Timestamp => backend::vk::QueryType::TIMESTAMP,
+			Self::Occlusion => backend::vk::QueryType::OCCLUSION,
+			Self::PipelineStatistics(_) => backend::vk::QueryType::PIPELINE_STATISTICS,
+		}
+	}
+
+	/// The number of values each query produces.
+	fn value_count(&self) -> usize {
+		match self {
+			Self::Timestamp | Self::Occlusion => 1,
+			Self::PipelineStatistics(statistics) => statistics.as_raw().count_ones() as usize,
+		}
+	}
+}
+
+/// The values of a [`pipeline statistics`](Kind::PipelineStatistics) query.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PipelineStatistics {
+	values: Vec<(flags::QueryPipelineStatistic, u64)>,
+}
+
+impl PipelineStatistics {
+	/// Returns the value of a single statistic, if it was enabled for the pool.
+	pub fn get(&self, statistic: flags::QueryPipelineStatistic) -> Option<u64> {
+		self.values
+			.iter()
+			.find(|(flag, _)| *flag == statistic)
+			.map(|(_, value)| *value)
+	}
+
+	/// Returns all of the enabled statistics and their values, in order of their bits.
+	pub fn iter(&self) -> impl Iterator<Item = &(flags::QueryPipelineStatistic, u64)> {
+		self.values.iter()
+	}
+}
+
+/// A collection of queries of a single [`Kind`], which the GPU writes results into.
+///
+/// Queries must be [`reset`](crate::command::Buffer::reset_queries) before they are used,
+/// and results are read on the CPU once the command buffer that wrote them has completed.
+///
+/// Equivalent to [`VkQueryPool`](https://www.khronos.org/registry/vulkan/specs/1.2-extensions/man/html/VkQueryPool.html).
+pub struct Pool {
+	internal: backend::vk::QueryPool,
+	device: sync::Arc<logical::Device>,
+	name: String,
+	kind: Kind,
+	count: u32,
+}
+
+impl Pool {
+	pub fn new(
+		device: &sync::Arc<logical::Device>,
+		name: &str,
+		kind: Kind,
+		count: u32,
+	) -> utility::Result<Self> {
+		let mut info = backend::vk::QueryPoolCreateInfo::builder()
+			.query_type(kind.as_vk())
+			.query_count(count);
+		if let Kind::PipelineStatistics(statistics) = kind {
+			info = info.pipeline_statistics(statistics);
+		}
+		let internal = unsafe { device.create_query_pool(&info, None) }?;
+		let pool = Self {
+			internal,
+			device: device.clone(),
+			name: name.to_owned(),
+			kind,
+			count,
+		};
+		device.set_object_name_logged(&pool.create_name(name));
+		Ok(pool)
+	}
+
+	pub fn kind(&self) -> Kind {
+		self.kind
+	}
+
+	/// The number of queries in the pool.
+	pub fn count(&self) -> u32 {
+		self.count
+	}
+
+	/// The number of nanoseconds it takes for a timestamp to be incremented by 1.
+	pub fn timestamp_period(&self) -> f32 {
+		self.device.physical().timestamp_period()
+	}
+
+	/// Reads the values of each query in the range, without blocking.
+	/// Queries which the GPU has not finished writing are None.
+	fn read(&self, queries: Range<u32>) -> utility::Result<Vec<Option<Vec<u64>>>> {
+		self.validate_range(&queries)?;
+		let query_count = queries.end.saturating_sub(queries.start) as usize;
+		if query_count == 0 {
+			return Ok(Vec::new());
+		}
+		// Each query is followed by its availability.
+		let stride = self.kind.value_count() + 1;
+		let mut data = vec![0u64; query_count * stride];
+		let device: &backend::Device = &self.device;
+		let result = unsafe {
+			(device.fp_v1_0().get_query_pool_results)(
+				device.handle(),
+				self.internal,
+				queries.start,
+				query_count as u32,
+				data.len() * std::mem::size_of::<u64>(),
+				data.as_mut_ptr() as *mut _,
+				(stride * std::mem::size_of::<u64>()) as u64,
+				backend::vk::QueryResultFlags::TYPE_64
+					| backend::vk::QueryResultFlags::WITH_AVAILABILITY,
+			)
+		};
+		match result {
+			backend::vk::Result::SUCCESS | backend::vk::Result::NOT_READY => {}
+			err => return Err(err.into()),
+		}
+		Ok(data
+			.chunks(stride)
+			.map(|query| match query[stride - 1] {
+				0 => None,
+				_ => Some(query[..stride - 1].to_vec()),
+			})
+			.collect())
+	}
+
+	/// Reads the raw timestamps (in ticks) of a range of [`timestamp`](Kind::Timestamp) queries.
+	/// Queries which are not yet available are None.
+	pub fn read_timestamps(&self, queries: Range<u32>) -> utility::Result<Vec<Option<u64>>> {
+		self.require_kind(Kind::Timestamp)?;
+		Ok(self
+			.read(queries)?
+			.into_iter()
+			.map(|values| values.map(|values| values[0]))
+			.collect())
+	}
+
+	/// Reads the timestamps of a range of [`timestamp`](Kind::Timestamp) queries,
+	/// converted to nanoseconds using the device's timestamp period.
+	/// Queries which are not yet available are None.
+	pub fn read_timestamps_ns(&self, queries: Range<u32>) -> utility::Result<Vec<Option<u64>>> {
+		let period = self.timestamp_period() as f64;
+		Ok(self
+			.read_timestamps(queries)?
+			.into_iter()
+			.map(|ticks| ticks.map(|ticks| (ticks as f64 * period) as u64))
+			.collect())
+	}
+
+	/// Reads the number of samples which passed in a range of [`occlusion`](Kind::Occlusion) queries.
+	/// Queries which are not yet available are None.
+	pub fn read_occlusion(&self, queries: Range<u32>) -> utility::Result<Vec<Option<u64>>> {
+		self.require_kind(Kind::Occlusion)?;
+		Ok(self
+			.read(queries)?
+			.into_iter()
+			.map(|values| values.map(|values| values[0]))
+			.collect())
+	}
+
+	/// Reads the statistics of a range of [`pipeline statistics`](Kind::PipelineStatistics) queries.
+	/// Queries which are not yet available are None.
+	pub fn read_pipeline_statistics(
+		&self,
+		queries: Range<u32>,
+	) -> utility::Result<Vec<Option<PipelineStatistics>>> {
+		let enabled = match self.kind {
+			Kind::PipelineStatistics(statistics) => statistics,
+			_ => {
+				return Err(utility::Error::InvalidQuery(format!(
+					"{:?} contains {:?} queries, not pipeline statistics",
+					self.name, self.kind
+				)))
+			}
+		};
+		// Statistics are written in order of their bits.
+		let flags = (0..32)
+			.map(|bit| flags::QueryPipelineStatistic::from_raw(1 << bit))
+			.filter(|flag| enabled.contains(*flag))
+			.collect::<Vec<_>>();
+		Ok(self
+			.read(queries)?
+			.into_iter()
+			.map(|values| {
+				values.map(|values| PipelineStatistics {
+					values: flags.iter().cloned().zip(values).collect(),
+				})
+			})
+			.collect())
+	}
+
+	/// Returns an error if the range is inverted or extends past the end of the pool.
+	pub fn validate_range(&self, queries: &Range<u32>) -> utility::Result<()> {
+		validate_range(self.count, queries)
+			.map_err(|err| utility::Error::InvalidQuery(format!("{:?} {}", self.name, err)))
+	}
+
+	/// Returns an error if the index is past the end of the pool.
+	pub fn validate_index(&self, index: u32) -> utility::Result<()> {
+		validate_index(self.count, index)
+			.map_err(|err| utility::Error::InvalidQuery(format!("{:?} {}", self.name, err)))
+	}
+
+	fn require_kind(&self, kind: Kind) -> utility::Result<()> {
+		match self.kind == kind {
+			true => Ok(()),
+			false => Err(utility::Error::InvalidQuery(format!(
+				"{:?} contains {:?} queries, not {:?}",
+				self.name, self.kind, kind
+			))),
+		}
+	}
+}
+
+fn validate_range(count: u32, queries: &Range<u32>) -> Result<(), String> {
+	if queries.start > queries.end || queries.end > count {
+		return Err(format!(
+			"has {} queries, so {:?} is not a valid range of queries",
+			count, queries
+		));
+	}
+	Ok(())
+}
+
+fn validate_index(count: u32, index: u32) -> Result<(), String> {
+	if index >= count {
+		return Err(format!(
+			"has {} queries, so {} is not a valid query index",
+			count, index
+		));
+	}
+	Ok(())
+}
+
+impl std::ops::Deref for Pool {
+	type Target = backend::vk::QueryPool;
+	fn deref(&self) -> &Self::Target {
+		&self.internal
+	}
+}
+
+impl Drop for Pool {
+	fn drop(&mut self) {
+		log::debug!(target: crate::LOG, "Dropping QueryPool: {:?}", self.name);
+		unsafe { self.device.destroy_query_pool(self.internal, None) };
+	}
+}
+
+impl HandledObject for Pool {
+	fn kind(&self) -> backend::vk::ObjectType {
+		<backend::vk::QueryPool as backend::vk::Handle>::TYPE
+	}
+
+	fn handle(&self) -> u64 {
+		use backend::vk::Handle;
+		self.internal.as_raw()
+	}
+}
+
+impl utility::NamedObject for Pool {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
+#[cfg(test)]
+mod ranges {
+	use super::*;
+
+	#[test]
+	fn within_pool() {
+		assert!(validate_range(8, &(0..8)).is_ok());
+		assert!(validate_range(8, &(3..3)).is_ok());
+	}
+
+	#[test]
+	fn out_of_bounds_or_inverted() {
+		assert!(validate_range(8, &(0..9)).is_err());
+		assert!(validate_range(8, &(8..9)).is_err());
+		#[allow(clippy::reversed_empty_ranges)]
+		let inverted = 5..2;
+		assert!(validate_range(8, &inverted).is_err());
+	}
+
+	#[test]
+	fn indices() {
+		assert!(validate_index(8, 0).is_ok());
+		assert!(validate_index(8, 7).is_ok());
+		assert!(validate_index(8, 8).is_err());
+		assert!(validate_index(0, 0).is_err());
+	}
+}
@@ -0,0 +1,245 @@
+use crate::{
+	command,
+	device::logical,
+	flags,
+	query::{Kind, Pool},
+	utility,
+};
+use std::sync;
+
+/// The GPU time spent between the start and end of a [`GpuProfiler`] scope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeTiming {
+	pub name: String,
+	pub duration_ns: u64,
+}
+
+impl ScopeTiming {
+	pub fn duration_ms(&self) -> f64 {
+		self.duration_ns as f64 / 1_000_000.0
+	}
+}
+
+/// Identifies a scope started by [`GpuProfiler::begin_scope`], which must be passed to [`GpuProfiler::end_scope`]
+/// in the same frame.
+#[must_use]
+pub struct ScopeToken {
+	frame: u64,
+	index: usize,
+}
+
+/// Measures how long the GPU spends executing named scopes of commands, using [`timestamp`](Kind::Timestamp) queries.
+///
+/// There is one query pool for each frame that can be in flight at once (like a [`PoolRing`](command::PoolRing)).
+/// When a frame comes back around in [`begin_frame`](GpuProfiler::begin_frame), its previous timings are read back
+/// and made available through [`results`](GpuProfiler::results).
+/// As with the pool ring, the commands from a frame's last use must have finished executing by then.
+///
+/// The timings are not forwarded to the [`profiling`] crate, which only exposes CPU scopes
+/// and has no way to submit GPU timestamps to its backends.
+/// Callers which want GPU timings in their profiler must read them from [`results`](GpuProfiler::results).
+pub struct GpuProfiler {
+	name: String,
+	frames: Vec<Frame>,
+	current: usize,
+	/// The number of frames which have been begun, used to reject scope tokens from earlier frames.
+	frame_number: u64,
+	timestamp_mask: u64,
+	results: Vec<ScopeTiming>,
+}
+
+struct Frame {
+	pool: sync::Arc<Pool>,
+	scopes: Vec<Scope>,
+}
+
+struct Scope {
+	name: String,
+	begin: u32,
+	end: Option<u32>,
+}
+
+impl GpuProfiler {
+	/// Creates a profiler which can record up to `max_scopes` scopes in each of `frame_count` frames,
+	/// on queues of the family `queue_family_index`.
+	/// Both `frame_count` and `max_scopes` must be greater than zero.
+	pub fn new(
+		device: &sync::Arc<logical::Device>,
+		name: &str,
+		queue_family_index: usize,
+		frame_count: usize,
+		max_scopes: u32,
+	) -> utility::Result<Self> {
+		assert!(
+			frame_count > 0,
+			"A GPU profiler requires at least one frame"
+		);
+		assert!(
+			max_scopes > 0,
+			"A GPU profiler requires at least one scope per frame"
+		);
+		let valid_bits = device.physical().timestamp_valid_bits(queue_family_index);
+		if valid_bits == 0 {
+			return Err(utility::Error::InvalidQuery(format!(
+				"queue family {} does not support timestamps",
+				queue_family_index
+			)));
+		}
+		let frames = (0..frame_count)
+			.map(|i| -> utility::Result<Frame> {
+				let pool = Pool::new(
+					device,
+					&format!("{}.Frame{}", name, i),
+					Kind::Timestamp,
+					max_scopes * 2,
+				)?;
+				Ok(Frame {
+					pool: sync::Arc::new(pool),
+					scopes: Vec::new(),
+				})
+			})
+			.collect::<utility::Result<Vec<_>>>()?;
+		Ok(Self {
+			name: name.to_owned(),
+			current: frames.len() - 1,
+			frames,
+			frame_number: 0,
+			timestamp_mask: timestamp_mask(valid_bits),
+			results: Vec::new(),
+		})
+	}
+
+	/// Moves to the next frame's queries, reading the timings it recorded the last time it was used,
+	/// then resets the queries using `buffer`.
+	/// Must be called once per frame before any scopes are recorded, outside of a render pass.
+	#[profiling::function]
+	pub fn begin_frame(&mut self, buffer: &mut command::Buffer) -> utility::Result<()> {
+		self.current = (self.current + 1) % self.frames.len();
+		self.frame_number += 1;
+		let frame = &mut self.frames[self.current];
+		self.results.clear();
+		if !frame.scopes.is_empty() {
+			let query_count = frame.scopes.len() as u32 * 2;
+			let timestamps = frame.pool.read_timestamps(0..query_count)?;
+			let period = frame.pool.timestamp_period();
+			for scope in frame.scopes.drain(..) {
+				let end = match scope.end {
+					Some(end) => end,
+					None => {
+						log::warn!(
+							target: crate::LOG,
+							"GPU scope {:?} of {:?} was never ended",
+							scope.name,
+							self.name
+						);
+						continue;
+					}
+				};
+				let (begin_ticks, end_ticks) =
+					match (timestamps[scope.begin as usize], timestamps[end as usize]) {
+						(Some(begin), Some(end)) => (begin, end),
+						_ => continue,
+					};
+				let timing = ScopeTiming {
+					duration_ns: ticks_to_ns(
+						end_ticks.wrapping_sub(begin_ticks) & self.timestamp_mask,
+						period,
+					),
+					name: scope.name,
+				};
+				self.results.push(timing);
+			}
+		}
+		buffer.reset_queries(&frame.pool, 0..frame.pool.count())?;
+		Ok(())
+	}
+
+	/// Writes the starting timestamp of a scope, once all previous commands have started executing.
+	pub fn begin_scope<TStr>(
+		&mut self,
+		buffer: &mut command::Buffer,
+		name: TStr,
+	) -> utility::Result<ScopeToken>
+	where
+		TStr: Into<String>,
+	{
+		let frame = &mut self.frames[self.current];
+		let begin = frame.scopes.len() as u32 * 2;
+		if begin + 1 >= frame.pool.count() {
+			return Err(utility::Error::InvalidQuery(format!(
+				"{:?} cannot record more than {} scopes per frame",
+				self.name,
+				frame.pool.count() / 2
+			)));
+		}
+		buffer.write_timestamp(&frame.pool, begin, flags::PipelineStage::TopOfPipe)?;
+		frame.scopes.push(Scope {
+			name: name.into(),
+			begin,
+			end: None,
+		});
+		Ok(ScopeToken {
+			frame: self.frame_number,
+			index: frame.scopes.len() - 1,
+		})
+	}
+
+	/// Writes the ending timestamp of a scope, once all previous commands have finished executing.
+	/// Returns an error if the scope was begun in an earlier frame.
+	pub fn end_scope(
+		&mut self,
+		buffer: &mut command::Buffer,
+		token: ScopeToken,
+	) -> utility::Result<()> {
+		let frame = &mut self.frames[self.current];
+		let scope = match frame.scopes.get_mut(token.index) {
+			Some(scope) if token.frame == self.frame_number => scope,
+			_ => {
+				return Err(utility::Error::InvalidQuery(format!(
+					"{:?} cannot end a scope from frame {} in frame {}",
+					self.name, token.frame, self.frame_number
+				)))
+			}
+		};
+		let end = scope.begin + 1;
+		buffer.write_timestamp(&frame.pool, end, flags::PipelineStage::BottomOfPipe)?;
+		scope.end = Some(end);
+		Ok(())
+	}
+
+	/// The timings read by the last call to [`begin_frame`](GpuProfiler::begin_frame), in the order the scopes were begun.
+	pub fn results(&self) -> &[ScopeTiming] {
+		&self.results
+	}
+}
+
+fn timestamp_mask(valid_bits: u32) -> u64 {
+	match valid_bits >= 64 {
+		true => u64::MAX,
+		false => (1u64 << valid_bits) - 1,
+	}
+}
+
+fn ticks_to_ns(ticks: u64, period: f32) -> u64 {
+	(ticks as f64 * period as f64) as u64
+}
+
+#[cfg(test)]
+mod timestamps {
+	use super::*;
+
+	#[test]
+	fn ticks_convert_with_period() {
+		assert_eq!(ticks_to_ns(1000, 1.0), 1000);
+		assert_eq!(ticks_to_ns(1000, 52.08), 52080);
+	}
+
+	#[test]
+	fn wrapped_timestamps_are_masked() {
+		let mask = timestamp_mask(36);
+		let begin = mask - 9;
+		let end = 10u64;
+		assert_eq!(end.wrapping_sub(begin) & mask, 20);
+		assert_eq!(timestamp_mask(64), u64::MAX);
+	}
+}
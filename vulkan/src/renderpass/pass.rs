@@ -53,6 +53,12 @@ impl Drop for Pass {
 	}
 }
 
+impl utility::NamedObject for Pass {
+	fn name(&self) -> &String {
+		&self.name
+	}
+}
+
 impl utility::HandledObject for Pass {
 	fn kind(&self) -> backend::vk::ObjectType {
 		<backend::vk::RenderPass as backend::vk::Handle>::TYPE
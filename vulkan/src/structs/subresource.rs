@@ -1,12 +1,18 @@
-pub use crate::{backend, flags::ImageAspect, utility};
+pub use crate::{
+	backend,
+	flags::{format, ImageAspect},
+	utility,
+};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct Range {
 	aspect: ImageAspect,
 	mip_level_start: u32,
-	mip_level_end_excl: u32,
+	/// The end of the mip levels, or None to include all of the remaining levels.
+	mip_level_end_excl: Option<u32>,
 	array_layer_start: u32,
-	array_layer_end_excl: u32,
+	/// The end of the array layers, or None to include all of the remaining layers.
+	array_layer_end_excl: Option<u32>,
 }
 
 impl Default for Range {
@@ -14,9 +20,9 @@ impl Default for Range {
 		Range {
 			aspect: ImageAspect::empty(),
 			mip_level_start: 0,
-			mip_level_end_excl: 1,
+			mip_level_end_excl: Some(1),
 			array_layer_start: 0,
-			array_layer_end_excl: 1,
+			array_layer_end_excl: Some(1),
 		}
 	}
 }
@@ -27,32 +33,69 @@ impl Range {
 		self
 	}
 
+	/// Includes all of the [`aspects`](format::Metadata::aspects) of a format
+	/// (i.e. both depth and stencil for a depth-stencil format).
+	pub fn with_format_aspects(self, format: format::Format) -> Self {
+		self.with_aspect(format::metadata(format).map_or(ImageAspect::COLOR, |info| info.aspects))
+	}
+
+	/// Sets the mip levels of the range. An unbounded end includes all of the remaining levels.
 	pub fn mips(mut self, range: impl std::ops::RangeBounds<u32>) -> Self {
-		self.mip_level_start = match range.start_bound() {
-			std::ops::Bound::Included(&i) => i,
-			std::ops::Bound::Excluded(&i) => i + 1,
-			std::ops::Bound::Unbounded => 0,
-		};
-		self.mip_level_end_excl = match range.end_bound() {
-			std::ops::Bound::Included(&i) => i + 1,
-			std::ops::Bound::Excluded(&i) => i,
-			std::ops::Bound::Unbounded => 0,
-		};
+		let (start, end) = Self::bounds(range);
+		self.mip_level_start = start;
+		self.mip_level_end_excl = end;
 		self
 	}
 
+	/// Sets the array layers of the range. An unbounded end includes all of the remaining layers.
 	pub fn layers(mut self, range: impl std::ops::RangeBounds<u32>) -> Self {
-		self.array_layer_start = match range.start_bound() {
+		let (start, end) = Self::bounds(range);
+		self.array_layer_start = start;
+		self.array_layer_end_excl = end;
+		self
+	}
+
+	pub(crate) fn aspect(&self) -> ImageAspect {
+		self.aspect
+	}
+
+	/// Returns the mip levels of the range within an image that has `count` levels.
+	/// An unbounded end includes all of the remaining levels.
+	pub(crate) fn mip_levels(&self, count: u32) -> std::ops::Range<u32> {
+		Self::clamp(self.mip_level_start, self.mip_level_end_excl, count)
+	}
+
+	/// Returns the array layers of the range within an image that has `count` layers.
+	/// An unbounded end includes all of the remaining layers.
+	pub(crate) fn array_layers(&self, count: u32) -> std::ops::Range<u32> {
+		Self::clamp(self.array_layer_start, self.array_layer_end_excl, count)
+	}
+
+	fn bounds(range: impl std::ops::RangeBounds<u32>) -> (u32, Option<u32>) {
+		let start = match range.start_bound() {
 			std::ops::Bound::Included(&i) => i,
-			std::ops::Bound::Excluded(&i) => i + 1,
+			std::ops::Bound::Excluded(&i) => i.saturating_add(1),
 			std::ops::Bound::Unbounded => 0,
 		};
-		self.array_layer_end_excl = match range.end_bound() {
-			std::ops::Bound::Included(&i) => i + 1,
-			std::ops::Bound::Excluded(&i) => i,
-			std::ops::Bound::Unbounded => 0,
+		let end = match range.end_bound() {
+			std::ops::Bound::Included(&i) => Some(i.saturating_add(1)),
+			std::ops::Bound::Excluded(&i) => Some(i),
+			std::ops::Bound::Unbounded => None,
 		};
-		self
+		(start, end)
+	}
+
+	/// Empty and inverted ranges contain no levels or layers.
+	fn clamp(start: u32, end_excl: Option<u32>, count: u32) -> std::ops::Range<u32> {
+		let start = start.min(count);
+		match end_excl {
+			Some(end) => start..end.max(start).min(count),
+			None => start..count,
+		}
+	}
+
+	fn count(start: u32, end_excl: Option<u32>, remaining: u32) -> u32 {
+		end_excl.map_or(remaining, |end| end.saturating_sub(start))
 	}
 }
 
@@ -61,13 +104,22 @@ impl Into<backend::vk::ImageSubresourceRange> for Range {
 		backend::vk::ImageSubresourceRange::builder()
 			.aspect_mask(self.aspect)
 			.base_mip_level(self.mip_level_start)
-			.level_count(self.mip_level_end_excl - self.mip_level_start)
+			.level_count(Self::count(
+				self.mip_level_start,
+				self.mip_level_end_excl,
+				backend::vk::REMAINING_MIP_LEVELS,
+			))
 			.base_array_layer(self.array_layer_start)
-			.layer_count(self.array_layer_end_excl - self.array_layer_start)
+			.layer_count(Self::count(
+				self.array_layer_start,
+				self.array_layer_end_excl,
+				backend::vk::REMAINING_ARRAY_LAYERS,
+			))
 			.build()
 	}
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct Layers {
 	aspect: ImageAspect,
 	mip_level: u32,
@@ -92,6 +144,12 @@ impl Layers {
 		self
 	}
 
+	/// Includes all of the [`aspects`](format::Metadata::aspects) of a format
+	/// (i.e. both depth and stencil for a depth-stencil format).
+	pub fn with_format_aspects(self, format: format::Format) -> Self {
+		self.with_aspect(format::metadata(format).map_or(ImageAspect::COLOR, |info| info.aspects))
+	}
+
 	pub fn mip(mut self, level: u32) -> Self {
 		self.mip_level = level;
 		self
@@ -122,3 +180,34 @@ impl Into<backend::vk::ImageSubresourceLayers> for Layers {
 			.build()
 	}
 }
+
+#[cfg(test)]
+mod range {
+	use super::*;
+
+	#[test]
+	fn bounded() {
+		let range = Range::default().mips(1..3).layers(2..=4);
+		assert_eq!(range.mip_levels(8), 1..3);
+		assert_eq!(range.array_layers(8), 2..5);
+		assert_eq!(range.mip_levels(2), 1..2);
+	}
+
+	#[test]
+	fn unbounded() {
+		let range = Range::default().mips(2..).layers(..);
+		assert_eq!(range.mip_levels(8), 2..8);
+		assert_eq!(range.array_layers(6), 0..6);
+		let vk: backend::vk::ImageSubresourceRange = range.into();
+		assert_eq!(vk.level_count, backend::vk::REMAINING_MIP_LEVELS);
+		assert_eq!(vk.layer_count, backend::vk::REMAINING_ARRAY_LAYERS);
+	}
+
+	#[test]
+	#[allow(clippy::reversed_empty_ranges)]
+	fn empty_and_inverted() {
+		assert_eq!(Range::default().mips(2..2).mip_levels(8), 2..2);
+		assert_eq!(Range::default().mips(5..2).mip_levels(8), 5..5);
+		assert_eq!(Range::default().layers(9..12).array_layers(8), 8..8);
+	}
+}
@@ -1,3 +1,6 @@
+mod access_state;
+pub use access_state::*;
+
 mod builder;
 pub use builder::*;
 
@@ -0,0 +1,39 @@
+use crate::flags::{AccessSet, StageSet};
+
+/// The pipeline stages and memory accesses of the most recently recorded use of a resource.
+/// Used to track [`image`](crate::image::Image) subresources and [`buffers`](crate::buffer::Buffer)
+/// so that [`transitions`](crate::command::barrier::Transition) can compute the barriers they require.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccessState {
+	pub stages: StageSet,
+	pub access: AccessSet,
+}
+
+impl AccessState {
+	pub fn new(stages: impl Into<StageSet>, access: impl Into<AccessSet>) -> Self {
+		Self {
+			stages: stages.into(),
+			access: access.into(),
+		}
+	}
+
+	/// Returns the accesses which write to memory, and therefore must be made available to later accesses.
+	pub fn writes(&self) -> AccessSet {
+		self.access.writes()
+	}
+
+	/// Returns true if a barrier must be placed between this state and a `next` use of the resource.
+	/// Any write before or after requires a dependency, but a read following other reads does not.
+	pub fn conflicts_with(&self, next: &Self) -> bool {
+		!self.access.is_empty() && (!self.writes().is_empty() || !next.writes().is_empty())
+	}
+
+	/// Combines two states which do not [`conflict`](Self::conflicts_with),
+	/// so that a later write waits on all of the accesses.
+	pub fn merge(&self, other: &Self) -> Self {
+		Self {
+			stages: self.stages | other.stages,
+			access: self.access | other.access,
+		}
+	}
+}
@@ -8,6 +8,18 @@ pub enum Error {
 	RequiresRenderChainUpdate,
 	General(std::io::Error),
 	InvalidBufferFormat(String),
+	InvalidImageUsage(String),
+	InvalidBufferUsage(String),
+	UnsupportedFormat(backend::vk::Format),
+	UntrackedResource(String),
+	InvalidOwnershipTransfer(String),
+	FeatureNotEnabled(String),
+	InvalidSubmission(String),
+	InvalidCommandPool(String),
+	InvalidPipeline(String),
+	InvalidQuery(String),
+	InvalidPushConstant(String),
+	InvalidClearRegion(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -25,6 +37,42 @@ impl std::fmt::Display for Error {
 			Error::InvalidBufferFormat(ref err) => {
 				write!(f, "Invalid buffer format: {}", err)
 			}
+			Error::InvalidImageUsage(ref err) => {
+				write!(f, "Invalid image usage: {}", err)
+			}
+			Error::InvalidBufferUsage(ref err) => {
+				write!(f, "Invalid buffer usage: {}", err)
+			}
+			Error::UnsupportedFormat(ref format) => {
+				write!(f, "Unsupported format: {:?}", format)
+			}
+			Error::UntrackedResource(ref name) => {
+				write!(f, "Resource state is not tracked: {}", name)
+			}
+			Error::InvalidOwnershipTransfer(ref err) => {
+				write!(f, "Invalid ownership transfer: {}", err)
+			}
+			Error::FeatureNotEnabled(ref feature) => {
+				write!(f, "Device feature not enabled: {}", feature)
+			}
+			Error::InvalidSubmission(ref err) => {
+				write!(f, "Invalid submission: {}", err)
+			}
+			Error::InvalidCommandPool(ref err) => {
+				write!(f, "Invalid command pool usage: {}", err)
+			}
+			Error::InvalidPipeline(ref err) => {
+				write!(f, "Invalid pipeline: {}", err)
+			}
+			Error::InvalidQuery(ref err) => {
+				write!(f, "Invalid query: {}", err)
+			}
+			Error::InvalidPushConstant(ref err) => {
+				write!(f, "Invalid push constant: {}", err)
+			}
+			Error::InvalidClearRegion(ref err) => {
+				write!(f, "Invalid clear region: {}", err)
+			}
 		}
 	}
 }
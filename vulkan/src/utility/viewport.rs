@@ -4,6 +4,7 @@ use crate::{
 };
 
 /// A 6-float struct representing the viewport of a window.
+#[derive(Clone, Copy, Debug)]
 pub struct Viewport {
 	pos: Offset2D,
 	size: Extent2D,